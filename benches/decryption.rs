@@ -0,0 +1,202 @@
+//! Decryption cost for large committees.
+//!
+//! Honest key generation is quadratic in the committee size, so the committees here are
+//! built from a known tau instead: every hint is computed directly as g to its value at
+//! tau. The hints are consistent, so agg_dec's checks pass and the key it recovers is right.
+
+use ark_ec::{pairing::Pairing, scalar_mul::fixed_base::FixedBase, CurveGroup, Group};
+use ark_ff::{batch_inversion, Field, PrimeField, UniformRand};
+use ark_poly::{univariate::DensePolynomial, DenseUVPolynomial, EvaluationDomain, Polynomial, Radix2EvaluationDomain};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use silent_threshold::{
+    decryption::{agg_dec, DecryptionContext},
+    encryption::encrypt,
+    kzg::UniversalParams,
+    setup::{AggregateKey, PublicKey},
+    signature::sign_aggregate,
+    utils::{interp_mostly_zero, interp_mostly_zero_on_domain},
+};
+
+type E = ark_bls12_381::Bls12_381;
+type F = <E as Pairing>::ScalarField;
+type G1 = <E as Pairing>::G1;
+type G2 = <E as Pairing>::G2;
+type UniPoly381 = DensePolynomial<F>;
+
+/// the quadratic interpolation agg_dec used before, as a baseline
+fn interp_naive(eval: F, points: &[F]) -> UniPoly381 {
+    let mut interp = UniPoly381::from_coefficients_vec(vec![F::ONE]);
+    for &point in &points[1..] {
+        interp = interp.naive_mul(&UniPoly381::from_coefficients_vec(vec![-point, F::ONE]));
+    }
+    let scale = interp.evaluate(&points[0]);
+    &interp * (eval / scale)
+}
+
+fn fixed_base<G: CurveGroup>(scalars: &[G::ScalarField]) -> Vec<G::Affine> {
+    let scalar_bits = F::MODULUS_BIT_SIZE as usize;
+    let window = FixedBase::get_mul_window_size(scalars.len());
+    let table = FixedBase::get_window_table(scalar_bits, window, G::generator());
+    G::normalize_batch(&FixedBase::msm::<G>(scalar_bits, window, &table, scalars))
+}
+
+/// params of degree n and a committee of n parties, with the slot 0 dummy party,
+/// built from tau and the secret keys as L_i(tau) = omega^i (tau^n - 1) / (n (tau - omega^i))
+fn committee(n: usize) -> (UniversalParams<E>, AggregateKey<E>, Vec<F>) {
+    let mut rng = ark_std::test_rng();
+    let tau = F::rand(&mut rng);
+    let powers_of_tau: Vec<F> = (0..=n).scan(F::ONE, |power, _| {
+        let current = *power;
+        *power *= tau;
+        Some(current)
+    }).collect();
+    let params = UniversalParams::new(fixed_base::<G1>(&powers_of_tau), fixed_base::<G2>(&powers_of_tau));
+
+    let mut sk: Vec<F> = (0..n).map(|_| F::rand(&mut rng)).collect();
+    sk[0] = F::ONE;
+
+    let domain = Radix2EvaluationDomain::<F>::new(n).unwrap();
+    let z = powers_of_tau[n] - F::ONE;
+    let n_inv = domain.size_inv;
+    let mut li: Vec<F> = domain.elements().map(|omega| tau - omega).collect();
+    batch_inversion(&mut li);
+    for (l, omega) in li.iter_mut().zip(domain.elements()) {
+        *l *= omega * z * n_inv;
+    }
+    let a: F = sk.iter().zip(&li).map(|(s, l)| *s * l).sum();
+
+    // bls_pk, sk_li, sk_li_minus0, sk_li_by_tau and the aggregated sk_li_by_z of every party
+    let (tau_inv, z_inv) = (tau.inverse().unwrap(), z.inverse().unwrap());
+    let mut scalars = sk.clone();
+    scalars.extend(sk.iter().zip(&li).map(|(s, l)| *s * l));
+    scalars.extend(sk.iter().zip(&li).map(|(s, l)| *s * (*l - n_inv)));
+    scalars.extend(sk.iter().zip(&li).map(|(s, l)| *s * (*l - n_inv) * tau_inv));
+    scalars.extend(sk.iter().zip(&li).map(|(s, l)| (a - s) * l * z_inv));
+    let points = fixed_base::<G1>(&scalars);
+    let [bls_pk, sk_li, sk_li_minus0, sk_li_by_tau, agg_sk_li_by_z]: [&[_]; 5] =
+        core::array::from_fn(|k| &points[k * n..(k + 1) * n]);
+
+    let pk: Vec<PublicKey<E>> = (0..n)
+        .map(|i| PublicKey {
+            id: i,
+            degree: n,
+            bls_pk: bls_pk[i].into(),
+            sk_li: sk_li[i].into(),
+            sk_li_minus0: sk_li_minus0[i].into(),
+            // agg_dec only reads the aggregated hints
+            sk_li_by_z: Vec::new(),
+            sk_li_by_tau: sk_li_by_tau[i].into(),
+            pop: G2::generator(),
+        })
+        .collect();
+
+    let h_minus1 = -G2::generator();
+    let agg_key = AggregateKey::from_parts(
+        pk,
+        n,
+        agg_sk_li_by_z.iter().map(|&p| p.into()).collect(),
+        G1::generator() * a,
+        params.powers_of_h[n] + h_minus1,
+        h_minus1,
+        E::pairing(params.powers_of_g[0], params.powers_of_h[0]),
+    );
+    (params, agg_key, sk)
+}
+
+fn bench_interp(c: &mut Criterion) {
+    let mut group = c.benchmark_group("interp_mostly_zero");
+    group.sample_size(10);
+
+    for log_n in [10, 12, 14, 16] {
+        let n = 1 << log_n;
+        let domain = Radix2EvaluationDomain::<F>::new(n).unwrap();
+
+        // half the committee is absent, and the case where almost everyone is
+        for (label, num_present) in [("half", n / 2), ("few", n / 16)] {
+            let absent: Vec<usize> = (num_present..n).collect();
+            let mut points = vec![domain.element(0)];
+            points.extend(absent.iter().map(|&i| domain.element(i)));
+
+            group.bench_with_input(BenchmarkId::new(format!("domain/{}", label), n), &n, |b, _| {
+                b.iter(|| interp_mostly_zero_on_domain(F::ONE, &domain, &absent))
+            });
+            group.bench_with_input(BenchmarkId::new(format!("tree/{}", label), n), &n, |b, _| {
+                b.iter(|| interp_mostly_zero(F::ONE, &points))
+            });
+            if log_n <= 12 {
+                group.bench_with_input(BenchmarkId::new(format!("naive/{}", label), n), &n, |b, _| {
+                    b.iter(|| interp_naive(F::ONE, &points))
+                });
+            }
+        }
+    }
+    group.finish();
+}
+
+fn bench_agg_dec(c: &mut Criterion) {
+    let mut group = c.benchmark_group("agg_dec");
+    group.sample_size(10);
+
+    for log_n in [10, 12, 14] {
+        let n = 1 << log_n;
+        let t = n / 2;
+        let (params, agg_key, sk) = committee(n);
+        let ct = encrypt::<E, _>(&agg_key, t, &params, &mut ark_std::test_rng());
+
+        // the dummy party and parties 1..=t respond
+        let mut partials: Vec<G2> = sk[..=t].iter().map(|s| ct.gamma_g2 * s).collect();
+        partials.resize(n, G2::default());
+        let mut selector = vec![true; t + 1];
+        selector.resize(n, false);
+        assert_eq!(agg_dec(&partials, &ct, &selector, &agg_key, &params).unwrap(), ct.enc_key);
+
+        group.bench_with_input(BenchmarkId::from_parameter(n), &n, |b, _| {
+            b.iter(|| agg_dec(&partials, &ct, &selector, &agg_key, &params).unwrap())
+        });
+
+        // per-ciphertext cost once the committee context exists. at n = 1024: 147 ms for agg_dec,
+        // 146 ms with a prepared ciphertext (prepare is 0.8 ms), 149 ms with the checked aggregate
+        let ctx = DecryptionContext::new(&agg_key, &params);
+        group.bench_with_input(BenchmarkId::new("context", n), &n, |b, _| {
+            b.iter(|| {
+                let prepared = ctx.prepare(&ct).unwrap();
+                ctx.agg_dec(&partials, &prepared, &selector).unwrap()
+            })
+        });
+        let prepared = ctx.prepare(&ct).unwrap();
+        group.bench_with_input(BenchmarkId::new("context/prepared", n), &n, |b, _| {
+            b.iter(|| ctx.agg_dec(&partials, &prepared, &selector).unwrap())
+        });
+        // with the opt-in check of the aggregate against the prepared fixed elements
+        group.bench_with_input(BenchmarkId::new("context/prepared/checked", n), &n, |b, _| {
+            b.iter(|| ctx.agg_dec_checked(&partials, &prepared, &selector).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_context(c: &mut Criterion) {
+    let mut group = c.benchmark_group("context");
+    let n = 1 << 10;
+    let t = n / 2;
+    let mut rng = ark_std::test_rng();
+    let (params, agg_key, _) = committee(n);
+    let ct = encrypt::<E, _>(&agg_key, t, &params, &mut ark_std::test_rng());
+    let ctx = DecryptionContext::new(&agg_key, &params);
+
+    // the preparation the context saves on each ciphertext
+    group.bench_function("prepare", |b| b.iter(|| ctx.prepare(&ct).unwrap()));
+
+    let h = G2::generator();
+    let partials: Vec<G2> = (0..n).map(|_| h * F::rand(&mut rng)).collect();
+    let selector = vec![true; n];
+    let sig = sign_aggregate(&partials, t, &selector, &agg_key, &params).unwrap();
+    group.bench_function("signature/verify", |b| b.iter(|| sig.verify(b"msg", t, &agg_key, &params)));
+    group.bench_function("signature/verify_with_context", |b| {
+        b.iter(|| sig.verify_with_context(b"msg", t, &ctx))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_interp, bench_agg_dec, bench_context);
+criterion_main!(benches);
@@ -0,0 +1,124 @@
+//! Hybrid encryption of files: the threshold ciphertext encapsulates a key, and the
+//! file is sealed with ChaCha20-Poly1305 under a key derived from it.
+//!
+//! format: MAGIC | label (canonical Vec<u8>) | ciphertext (canonical) | nonce (12 bytes) | sealed payload
+//!
+//! the ciphertext is stored with enc_key set to the identity, so that the envelope
+//! does not carry the key it encapsulates
+
+use ark_ec::pairing::PairingOutput;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+use ark_std::rand::{CryptoRng, RngCore};
+use ark_std::Zero;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use sha2::{Digest, Sha256};
+use silent_threshold::encryption::{encrypt_with_label, encrypt_with_proof, Ciphertext};
+use silent_threshold::kzg::UniversalParams;
+use silent_threshold::setup::AggregateKey;
+
+use crate::E;
+
+pub const MAGIC: &[u8; 8] = b"STHYBRD1";
+
+const KEY_DST: &[u8] = b"SILENT-THRESHOLD-V01-CS01-HYBRID-CHACHA20POLY1305";
+
+pub struct Envelope {
+    pub label: Vec<u8>,
+    pub ct: Ciphertext<E>,
+    nonce: [u8; 12],
+    payload: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    /// The payload was modified, or the key did not decapsulate correctly.
+    Decryption,
+}
+
+impl Envelope {
+    /// encrypts plaintext to the committee behind agg_key, decryptable by any t parties
+    pub fn seal<R: RngCore + CryptoRng>(
+        agg_key: &AggregateKey<E>,
+        params: &UniversalParams<E>,
+        t: usize,
+        label: &[u8],
+        prove_well_formed: bool,
+        plaintext: &[u8],
+        rng: &mut R,
+    ) -> Self {
+        let mut ct = if prove_well_formed {
+            encrypt_with_proof(agg_key, t, params, label, rng)
+        } else {
+            encrypt_with_label(agg_key, t, params, label, rng)
+        };
+        let enc_key = std::mem::replace(&mut ct.enc_key, PairingOutput::zero());
+
+        let mut nonce = [0u8; 12];
+        rng.fill_bytes(&mut nonce);
+        let mut envelope = Envelope {
+            label: label.to_vec(),
+            ct,
+            nonce,
+            payload: Vec::new(),
+        };
+        envelope.payload = cipher(&enc_key)
+            .encrypt(Nonce::from_slice(&nonce), Payload { msg: plaintext, aad: &envelope.header() })
+            .expect("encryption of an in-memory buffer does not fail");
+        envelope
+    }
+
+    /// decrypts the payload with the key recovered by combining partial decryptions
+    pub fn open(&self, enc_key: &PairingOutput<E>) -> Result<Vec<u8>, Error> {
+        cipher(enc_key)
+            .decrypt(Nonce::from_slice(&self.nonce), Payload { msg: &self.payload, aad: &self.header() })
+            .map_err(|_| Error::Decryption)
+    }
+
+    pub fn payload_len(&self) -> usize {
+        self.payload.len()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.header();
+        bytes.extend_from_slice(&self.nonce);
+        bytes.extend_from_slice(&self.payload);
+        bytes
+    }
+
+    /// None if bytes do not start with MAGIC
+    pub fn from_bytes(bytes: &[u8]) -> Option<Result<Self, SerializationError>> {
+        let mut reader = bytes.strip_prefix(MAGIC)?;
+        Some((|| {
+            let label = Vec::<u8>::deserialize_uncompressed(&mut reader)?;
+            let ct = Ciphertext::<E>::deserialize_uncompressed(&mut reader)?;
+            if reader.len() < 12 {
+                return Err(SerializationError::InvalidData);
+            }
+            let (nonce, payload) = reader.split_at(12);
+            Ok(Envelope {
+                label,
+                ct,
+                nonce: nonce.try_into().unwrap(),
+                payload: payload.to_vec(),
+            })
+        })())
+    }
+
+    /// everything before the nonce, authenticated as associated data
+    fn header(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        self.label.serialize_uncompressed(&mut bytes).unwrap();
+        self.ct.serialize_uncompressed(&mut bytes).unwrap();
+        bytes
+    }
+}
+
+fn cipher(enc_key: &PairingOutput<E>) -> ChaCha20Poly1305 {
+    let mut hasher = Sha256::new();
+    hasher.update(KEY_DST);
+    let mut bytes = Vec::new();
+    enc_key.serialize_compressed(&mut bytes).unwrap();
+    hasher.update(&bytes);
+    ChaCha20Poly1305::new(Key::from_slice(&hasher.finalize()))
+}
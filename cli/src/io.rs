@@ -0,0 +1,110 @@
+//! reading and writing objects as files or pipes. "-" stands for stdin/stdout.
+//! objects are written in the crate's canonical uncompressed encoding, or as hex with --hex;
+//! either is accepted on input
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+
+use crate::Result;
+
+pub fn read_raw(path: &Path) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    if path == Path::new("-") {
+        io::stdin().read_to_end(&mut bytes)?;
+    } else {
+        File::open(path)
+            .and_then(|mut f| f.read_to_end(&mut bytes))
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+    }
+    Ok(bytes)
+}
+
+/// writes bytes to path, or stdout for "-". secret files are created readable by the owner only
+pub fn write_raw(path: &Path, bytes: &[u8], secret: bool) -> Result<()> {
+    if path == Path::new("-") {
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(bytes)?;
+        stdout.flush()?;
+        return Ok(());
+    }
+
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    if secret {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    #[cfg(not(unix))]
+    let _ = secret;
+
+    options
+        .open(path)
+        .and_then(|mut f| f.write_all(bytes))
+        .map_err(|e| format!("{}: {}", path.display(), e).into())
+}
+
+/// the bytes of an object file, hex decoded if the file is hex text
+pub fn read_object_bytes(path: &Path) -> Result<Vec<u8>> {
+    let bytes = read_raw(path)?;
+    Ok(decode_hex(&bytes).unwrap_or(bytes))
+}
+
+pub fn read_object<T: CanonicalDeserialize>(path: &Path) -> Result<T> {
+    let bytes = read_object_bytes(path)?;
+    checked(&bytes).map_err(|e| format!("{}: {}", path.display(), e).into())
+}
+
+pub fn write_object<T: CanonicalSerialize>(path: &Path, obj: &T, hex: bool, secret: bool) -> Result<()> {
+    let mut bytes = Vec::new();
+    obj.serialize_uncompressed(&mut bytes)?;
+    write_bytes(path, &bytes, hex, secret)
+}
+
+/// writes bytes that are already in the binary format, hex encoding them if asked to
+pub fn write_bytes(path: &Path, bytes: &[u8], hex: bool, secret: bool) -> Result<()> {
+    if hex {
+        let mut text = hex::encode(bytes).into_bytes();
+        text.push(b'\n');
+        write_raw(path, &text, secret)
+    } else {
+        write_raw(path, bytes, secret)
+    }
+}
+
+/// deserializes and validates a whole buffer, rejecting trailing bytes
+pub fn checked<T: CanonicalDeserialize>(mut bytes: &[u8]) -> std::result::Result<T, SerializationError> {
+    let value = T::deserialize_uncompressed(&mut bytes)?;
+    if !bytes.is_empty() {
+        return Err(SerializationError::InvalidData);
+    }
+    Ok(value)
+}
+
+/// None unless bytes are hex digits, optionally surrounded by whitespace
+fn decode_hex(bytes: &[u8]) -> Option<Vec<u8>> {
+    let text = std::str::from_utf8(bytes).ok()?.trim();
+    if text.is_empty() {
+        return None;
+    }
+    hex::decode(text).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_hex() {
+        assert_eq!(decode_hex(b"00ff10\n"), Some(vec![0, 255, 16]));
+        assert_eq!(decode_hex(b"0ff"), None);
+        assert_eq!(decode_hex(b"\x00\xff"), None);
+        assert_eq!(decode_hex(b"  \n"), None);
+
+        assert!(checked::<u64>(&[1, 0, 0, 0, 0, 0, 0, 0]).is_ok());
+        assert!(checked::<u64>(&[1, 0, 0, 0, 0, 0, 0, 0, 0]).is_err());
+    }
+}
@@ -0,0 +1,438 @@
+//! silent-threshold: runs the protocol from the command line, one subcommand per step
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use ark_ec::pairing::{Pairing, PairingOutput};
+use ark_poly::univariate::DensePolynomial;
+use ark_std::Zero;
+use clap::{Parser, Subcommand};
+use rand::rngs::OsRng;
+use silent_threshold::decryption::DecryptionContext;
+use silent_threshold::encryption::{Ciphertext, EncryptionKey};
+use silent_threshold::import;
+use silent_threshold::kzg::{UniversalParams, KZG10};
+use silent_threshold::setup::{AggregateKey, PublicKey, SecretKey};
+use zeroize::Zeroizing;
+
+mod envelope;
+mod io;
+mod object;
+
+use envelope::Envelope;
+use io::{read_object, read_object_bytes, read_raw, write_bytes, write_object, write_raw};
+use object::{Object, Partial};
+
+type E = ark_bls12_381::Bls12_381;
+type G2 = <E as Pairing>::G2;
+type UniPoly381 = DensePolynomial<<E as Pairing>::ScalarField>;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+#[derive(Parser)]
+#[command(name = "silent-threshold", version, about = "Silent threshold encryption over BLS12-381")]
+struct Cli {
+    /// write objects as hex text instead of binary (inputs may be either)
+    #[arg(long, global = true)]
+    hex: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// generate params for a committee of DEGREE parties, or import them from a ceremony
+    Setup {
+        #[arg(long)]
+        degree: usize,
+        /// import the powers of tau from a snarkjs .ptau file over BLS12-381
+        #[arg(long, conflicts_with = "ethereum_json")]
+        ptau: Option<PathBuf>,
+        /// import the powers of tau from the Ethereum KZG ceremony JSON (degree at most 64)
+        #[arg(long)]
+        ethereum_json: Option<PathBuf>,
+        #[arg(short, long, default_value = "-")]
+        out: PathBuf,
+    },
+    /// generate the secret key and public key of party ID in a committee of N slots.
+    /// slot 0 is the dummy party, added by aggregate
+    Keygen {
+        #[arg(long)]
+        params: PathBuf,
+        #[arg(long)]
+        id: usize,
+        /// committee size including the dummy party, a power of two equal to the params degree
+        #[arg(long)]
+        n: usize,
+        #[arg(long)]
+        sk_out: PathBuf,
+        #[arg(long)]
+        pk_out: PathBuf,
+    },
+    /// aggregate the public keys of slots 1..n into the committee's aggregate key
+    Aggregate {
+        #[arg(long)]
+        params: PathBuf,
+        #[arg(short, long, default_value = "-")]
+        out: PathBuf,
+        #[arg(required = true)]
+        pk: Vec<PathBuf>,
+    },
+    /// encrypt a file so that any T parties can decrypt it together
+    Encrypt {
+        #[arg(long)]
+        params: PathBuf,
+        #[arg(long)]
+        agg_key: PathBuf,
+        #[arg(long)]
+        t: usize,
+        /// associated data the ciphertext is bound to
+        #[arg(long, default_value = "")]
+        label: String,
+        /// attach a well-formedness proof
+        #[arg(long)]
+        proof: bool,
+        #[arg(short, long, default_value = "-")]
+        out: PathBuf,
+        #[arg(default_value = "-")]
+        input: PathBuf,
+    },
+    /// check an encrypted file or ciphertext and release this party's partial decryption
+    PartialDecrypt {
+        #[arg(long)]
+        params: PathBuf,
+        #[arg(long)]
+        agg_key: PathBuf,
+        #[arg(long)]
+        sk: PathBuf,
+        #[arg(short, long, default_value = "-")]
+        out: PathBuf,
+        #[arg(default_value = "-")]
+        input: PathBuf,
+    },
+    /// combine partial decryptions and decrypt. a bare ciphertext yields the encapsulated key
+    Combine {
+        #[arg(long)]
+        params: PathBuf,
+        #[arg(long)]
+        agg_key: PathBuf,
+        #[arg(short, long, default_value = "-")]
+        out: PathBuf,
+        input: PathBuf,
+        #[arg(required = true)]
+        partials: Vec<PathBuf>,
+    },
+    /// print what an object is and its public metadata
+    Inspect {
+        #[arg(default_value = "-")]
+        input: PathBuf,
+    },
+    /// check an object: proofs of possession, ciphertext proofs and bindings, partial decryptions
+    Verify {
+        #[arg(long)]
+        params: Option<PathBuf>,
+        #[arg(long)]
+        agg_key: Option<PathBuf>,
+        /// the ciphertext or encrypted file a partial decryption is for
+        #[arg(long)]
+        ciphertext: Option<PathBuf>,
+        #[arg(default_value = "-")]
+        input: PathBuf,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let hex = cli.hex;
+    match cli.command {
+        Command::Setup { degree, ptau, ethereum_json, out } => {
+            let params = match (ptau, ethereum_json) {
+                (Some(path), _) => import::from_ptau_file(path, degree).map_err(debug)?,
+                (_, Some(path)) => import::from_ethereum_json_file(path, degree).map_err(debug)?,
+                _ => KZG10::<E, UniPoly381>::setup(degree, &mut OsRng).map_err(debug)?,
+            };
+            write_object(&out, &params, hex, false)
+        }
+        Command::Keygen { params, id, n, sk_out, pk_out } => {
+            let params: UniversalParams<E> = read_object(&params)?;
+            if !n.is_power_of_two() || n < 2 {
+                return Err(format!("n = {} must be a power of two, at least 2", n).into());
+            }
+            if n != params.degree() {
+                return Err(format!("n = {} must equal the params degree {}", n, params.degree()).into());
+            }
+            if id == 0 || id >= n {
+                return Err(format!("id must be in 1..{}, slot 0 is the dummy party", n).into());
+            }
+            if sk_out == Path::new("-") && pk_out == Path::new("-") {
+                return Err("--sk-out and --pk-out cannot both be stdout".into());
+            }
+
+            let sk = SecretKey::<E>::new(&mut OsRng);
+            let pk = sk.get_pk(id, &params, n);
+            write_bytes(&sk_out, &sk.export_secret_bytes(), hex, true)?;
+            write_object(&pk_out, &pk, hex, false)
+        }
+        Command::Aggregate { params, out, pk } => {
+            let params: UniversalParams<E> = read_object(&params)?;
+            let agg_key = aggregate(&params, &pk)?;
+            write_object(&out, &agg_key, hex, false)
+        }
+        Command::Encrypt { params, agg_key, t, label, proof, out, input } => {
+            let params: UniversalParams<E> = read_object(&params)?;
+            let agg_key = read_agg_key(&agg_key, &params)?;
+            let n = agg_key.pk.len();
+            if t == 0 || t >= n {
+                return Err(format!("t = {} must be in 1..{}", t, n).into());
+            }
+
+            let plaintext = read_raw(&input)?;
+            let envelope = Envelope::seal(&agg_key, &params, t, label.as_bytes(), proof, &plaintext, &mut OsRng);
+            write_bytes(&out, &envelope.to_bytes(), hex, false)
+        }
+        Command::PartialDecrypt { params, agg_key, sk, out, input } => {
+            let params: UniversalParams<E> = read_object(&params)?;
+            let agg_key = read_agg_key(&agg_key, &params)?;
+            let sk = read_secret_key(&sk)?;
+            let (ct, label) = read_ciphertext(&input)?;
+            check_ciphertext(&ct, &label, &agg_key, &params)?;
+
+            // the slot is found from the key itself, so a key cannot answer for someone else's slot
+            let bls_pk = sk.bls_pk();
+            let id = agg_key
+                .pk
+                .iter()
+                .position(|pk| pk.bls_pk == bls_pk)
+                .ok_or("the secret key is not part of this committee")?;
+
+            let partial = Partial {
+                id,
+                value: sk.partial_decryption(&ct, &label, &agg_key, &params).map_err(debug)?,
+            };
+            write_object(&out, &partial, hex, false)
+        }
+        Command::Combine { params, agg_key, out, input, partials } => {
+            let params: UniversalParams<E> = read_object(&params)?;
+            let agg_key = read_agg_key(&agg_key, &params)?;
+            let bytes = read_object_bytes(&input)?;
+            let partials = partials.iter().map(|p| read_object(p)).collect::<Result<Vec<Partial>>>()?;
+
+            match Envelope::from_bytes(&bytes) {
+                Some(envelope) => {
+                    let envelope = envelope?;
+                    let enc_key = combine(&envelope.ct, &envelope.label, &partials, &agg_key, &params)?;
+                    let plaintext = envelope.open(&enc_key).map_err(debug)?;
+                    write_raw(&out, &plaintext, false)
+                }
+                None => {
+                    let ct: Ciphertext<E> = io::checked(&bytes)?;
+                    let enc_key = combine(&ct, &[], &partials, &agg_key, &params)?;
+                    write_object(&out, &enc_key, hex, false)
+                }
+            }
+        }
+        Command::Inspect { input } => {
+            let object = detect(&input)?;
+            println!("{}", object.kind());
+            for (name, value) in object.describe() {
+                println!("  {}: {}", name, value);
+            }
+            Ok(())
+        }
+        Command::Verify { params, agg_key, ciphertext, input } => {
+            let params = params.map(|p| read_object::<UniversalParams<E>>(&p)).transpose()?;
+            let agg_key = agg_key
+                .map(|p| read_agg_key(&p, params.as_ref().ok_or("reading --agg-key needs --params")?))
+                .transpose()?;
+            let object = detect(&input)?;
+            verify(&object, params.as_ref(), agg_key.as_ref(), ciphertext.as_deref())?;
+            println!("ok: {}", object.kind());
+            Ok(())
+        }
+    }
+}
+
+/// the aggregate key over the dummy party and the public keys of slots 1..n, in any order
+fn aggregate(params: &UniversalParams<E>, paths: &[PathBuf]) -> Result<AggregateKey<E>> {
+    let mut pk = paths.iter().map(|p| read_object(p)).collect::<Result<Vec<PublicKey<E>>>>()?;
+    pk.sort_by_key(|pk| pk.id);
+
+    let n = pk[0].sk_li_by_z.len();
+    if let Some(pki) = pk.iter().find(|pki| pki.sk_li_by_z.len() != n) {
+        return Err(format!("public key {} is for a committee of {}, not {}", pki.id, pki.sk_li_by_z.len(), n).into());
+    }
+    for slot in 1..n.max(pk.len() + 1) {
+        if pk.iter().filter(|pki| pki.id == slot).count() != 1 {
+            return Err(format!("expected one public key for each slot in 1..{}, slot {} has none or several", n, slot).into());
+        }
+    }
+
+    // the dummy party's key is public, so anyone aggregating derives the same one
+    let mut dummy = SecretKey::<E>::new(&mut OsRng);
+    dummy.nullify();
+    pk.insert(0, dummy.get_pk(0, params, n));
+
+    Ok(AggregateKey::new(pk, params).map_err(debug)?)
+}
+
+/// recovers the encapsulated key from the first t partial decryptions that verify.
+/// the dummy party's partial decryption is gamma_g2 itself and is added here
+fn combine(
+    ct: &Ciphertext<E>,
+    label: &[u8],
+    partials: &[Partial],
+    agg_key: &AggregateKey<E>,
+    params: &UniversalParams<E>,
+) -> Result<PairingOutput<E>> {
+    let ctx = DecryptionContext::new(agg_key, params);
+    let prepared = ctx.prepare(ct).map_err(debug)?;
+    if !ct.verify(label, agg_key, params) {
+        return Err("the ciphertext does not verify against its label".into());
+    }
+
+    let n = agg_key.pk.len();
+    let mut values = vec![G2::zero(); n];
+    let mut selector = vec![false; n];
+    values[0] = ct.gamma_g2;
+    selector[0] = true;
+    if !agg_key.pk[0].verify_partial_decryption(ct, &values[0]) {
+        return Err("slot 0 of the aggregate key is not the dummy party".into());
+    }
+
+    let mut selected = 0;
+    for partial in partials {
+        if selected == ct.t {
+            break;
+        }
+        if partial.id == 0 || partial.id >= n {
+            return Err(format!("partial decryption for slot {} outside 1..{}", partial.id, n).into());
+        }
+        if selector[partial.id] {
+            continue;
+        }
+        if !agg_key.pk[partial.id].verify_partial_decryption(ct, &partial.value) {
+            eprintln!("warning: partial decryption from party {} does not verify, skipping it", partial.id);
+            continue;
+        }
+        values[partial.id] = partial.value;
+        selector[partial.id] = true;
+        selected += 1;
+    }
+    if selected < ct.t {
+        return Err(format!("{} valid partial decryptions, the threshold is {}", selected, ct.t).into());
+    }
+
+    Ok(ctx.agg_dec(&values, &prepared, &selector).map_err(debug)?)
+}
+
+/// the aggregate key is stored as its public keys and aggregated again, so every check in new runs
+fn read_agg_key(path: &Path, params: &UniversalParams<E>) -> Result<AggregateKey<E>> {
+    let bytes = read_object_bytes(path)?;
+    AggregateKey::from_bytes(&bytes, params).map_err(|e| format!("{}: {:?}", path.display(), e).into())
+}
+
+fn read_secret_key(path: &Path) -> Result<SecretKey<E>> {
+    let bytes = Zeroizing::new(read_object_bytes(path)?);
+    SecretKey::from_secret_bytes(&bytes).map_err(|e| format!("{}: {}", path.display(), e).into())
+}
+
+/// reads an encrypted file or a bare ciphertext, with the label it is bound to
+fn read_ciphertext(path: &Path) -> Result<(Ciphertext<E>, Vec<u8>)> {
+    match detect(path)? {
+        Object::Envelope(envelope) => Ok((envelope.ct, envelope.label)),
+        Object::Ciphertext(ct) => Ok((ct, Vec::new())),
+        other => Err(format!("{}: expected a ciphertext, found {}", path.display(), other.kind()).into()),
+    }
+}
+
+/// what a party checks before releasing a partial decryption
+fn check_ciphertext(
+    ct: &Ciphertext<E>,
+    label: &[u8],
+    agg_key: &AggregateKey<E>,
+    params: &UniversalParams<E>,
+) -> Result<()> {
+    DecryptionContext::new(agg_key, params).prepare(ct).map_err(debug)?;
+    if !ct.verify(label, agg_key, params) {
+        return Err("the ciphertext does not verify against its label".into());
+    }
+    if ct.wf_proof.is_some() && !ct.verify_well_formed(&EncryptionKey::new(agg_key, params)) {
+        return Err("the well-formedness proof does not verify".into());
+    }
+    Ok(())
+}
+
+fn verify(
+    object: &Object,
+    params: Option<&UniversalParams<E>>,
+    agg_key: Option<&AggregateKey<E>>,
+    ciphertext: Option<&Path>,
+) -> Result<()> {
+    // params, and every object's encoding, are already checked when they are read
+    let check_degree = |degree: usize| match params {
+        Some(params) if params.degree() != degree => {
+            Err(format!("degree {} does not match the params degree {}", degree, params.degree()))
+        }
+        _ => Ok(()),
+    };
+
+    match object {
+        Object::Params(_) => Ok(()),
+        Object::PublicKey(pk) => {
+            if !pk.verify_possession() {
+                return Err(format!("the proof of possession of public key {} does not verify", pk.id).into());
+            }
+            Ok(check_degree(pk.degree)?)
+        }
+        Object::AggregateKey(pk) => {
+            let params = params.ok_or("verifying an aggregate key needs --params")?;
+            AggregateKey::new(pk.clone(), params).map_err(debug)?;
+            Ok(())
+        }
+        Object::Envelope(_) | Object::Ciphertext(_) => {
+            let (params, agg_key) = params.zip(agg_key).ok_or("verifying a ciphertext needs --params and --agg-key")?;
+            let (ct, label) = match object {
+                Object::Envelope(envelope) => (&envelope.ct, &envelope.label[..]),
+                Object::Ciphertext(ct) => (ct, &[][..]),
+                _ => unreachable!(),
+            };
+            check_ciphertext(ct, label, agg_key, params)
+        }
+        Object::Partial(partial) => {
+            let ((params, agg_key), path) = params
+                .zip(agg_key)
+                .zip(ciphertext)
+                .ok_or("verifying a partial decryption needs --params, --agg-key and --ciphertext")?;
+            // a partial is only worth checking for a ciphertext a party would have answered
+            let (ct, label) = read_ciphertext(path)?;
+            check_ciphertext(&ct, &label, agg_key, params)?;
+            let pk = agg_key.pk.get(partial.id).ok_or("the partial decryption is for a slot outside the committee")?;
+            if !pk.verify_partial_decryption(&ct, &partial.value) {
+                return Err(format!("the partial decryption from party {} does not verify", partial.id).into());
+            }
+            Ok(())
+        }
+        Object::SecretKey => Err("secret keys carry nothing to verify".into()),
+    }
+}
+
+fn detect(path: &Path) -> Result<Object> {
+    let bytes = read_object_bytes(path)?;
+    Object::detect(&bytes).ok_or_else(|| format!("{}: not an object in the silent-threshold format", path.display()).into())
+}
+
+/// the library's error enums are Debug only
+fn debug<T: std::fmt::Debug>(e: T) -> String {
+    format!("{:?}", e)
+}
@@ -0,0 +1,113 @@
+//! the objects the CLI reads and writes, told apart by trying each encoding in turn
+
+use ark_ec::pairing::PairingOutput;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::Zero;
+use silent_threshold::encryption::Ciphertext;
+use silent_threshold::kzg::UniversalParams;
+use silent_threshold::setup::{PublicKey, SecretKey};
+
+use crate::envelope::Envelope;
+use crate::io::checked;
+use crate::{E, G2};
+
+/// a partial decryption together with the slot id of the party that produced it
+#[derive(CanonicalSerialize, CanonicalDeserialize)]
+pub struct Partial {
+    pub id: usize,
+    pub value: G2,
+}
+
+pub enum Object {
+    Envelope(Envelope),
+    Ciphertext(Ciphertext<E>),
+    Params(UniversalParams<E>),
+    /// the public keys an aggregate key is stored as, slot 0 first
+    AggregateKey(Vec<PublicKey<E>>),
+    PublicKey(PublicKey<E>),
+    Partial(Partial),
+    /// recognized, but never kept or shown
+    SecretKey,
+}
+
+impl Object {
+    pub fn detect(bytes: &[u8]) -> Option<Self> {
+        if let Some(envelope) = Envelope::from_bytes(bytes) {
+            return envelope.ok().map(Object::Envelope);
+        }
+        // cheapest and most specific encodings first; params validate with pairings
+        if SecretKey::<E>::from_secret_bytes(bytes).is_ok() {
+            return Some(Object::SecretKey);
+        }
+        if let Ok(partial) = checked(bytes) {
+            return Some(Object::Partial(partial));
+        }
+        if let Ok(ct) = checked(bytes) {
+            return Some(Object::Ciphertext(ct));
+        }
+        if let Ok(pk) = checked(bytes) {
+            return Some(Object::PublicKey(pk));
+        }
+        if let Ok(pk) = checked(bytes) {
+            return Some(Object::AggregateKey(pk));
+        }
+        checked(bytes).ok().map(Object::Params)
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Object::Envelope(_) => "encrypted file",
+            Object::Ciphertext(_) => "ciphertext",
+            Object::Params(_) => "params",
+            Object::AggregateKey(_) => "aggregate key",
+            Object::PublicKey(_) => "public key",
+            Object::Partial(_) => "partial decryption",
+            Object::SecretKey => "secret key",
+        }
+    }
+
+    /// the fields shown by inspect, never including secret material
+    pub fn describe(&self) -> Vec<(&'static str, String)> {
+        match self {
+            Object::Envelope(envelope) => {
+                let mut fields = vec![
+                    ("label", hex::encode(&envelope.label)),
+                    ("payload bytes", envelope.payload_len().to_string()),
+                ];
+                fields.extend(describe_ciphertext(&envelope.ct));
+                fields
+            }
+            Object::Ciphertext(ct) => describe_ciphertext(ct),
+            Object::Params(params) => vec![
+                ("degree", params.degree().to_string()),
+                ("powers of h", params.powers_of_h.len().to_string()),
+                ("fingerprint", hex::encode(params.fingerprint())),
+            ],
+            Object::AggregateKey(pk) => vec![
+                ("parties", pk.len().to_string()),
+                ("degree", pk.first().map_or(0, |pk| pk.degree).to_string()),
+            ],
+            Object::PublicKey(pk) => vec![
+                ("id", pk.id.to_string()),
+                ("parties", pk.sk_li_by_z.len().to_string()),
+                ("degree", pk.degree.to_string()),
+            ],
+            Object::Partial(partial) => vec![("id", partial.id.to_string())],
+            Object::SecretKey => Vec::new(),
+        }
+    }
+}
+
+fn describe_ciphertext(ct: &Ciphertext<E>) -> Vec<(&'static str, String)> {
+    let mut fields = vec![
+        ("threshold", ct.t.to_string()),
+        ("degree", ct.degree.to_string()),
+        ("params fingerprint", hex::encode(ct.params_fingerprint)),
+        ("aggregate key fingerprint", hex::encode(ct.agg_key_fingerprint)),
+        ("well-formedness proof", if ct.wf_proof.is_some() { "yes" } else { "no" }.to_string()),
+    ];
+    if ct.enc_key != PairingOutput::zero() {
+        fields.push(("warning", "carries the encapsulated key in the clear".to_string()));
+    }
+    fields
+}
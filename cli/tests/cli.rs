@@ -0,0 +1,116 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+fn run(dir: &Path, args: &[&str], stdin: Option<&[u8]>) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_silent-threshold"))
+        .current_dir(dir)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(stdin.unwrap_or_default()).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn ok(dir: &Path, args: &[&str]) -> Vec<u8> {
+    let out = run(dir, args, None);
+    assert!(out.status.success(), "{:?}: {}", args, String::from_utf8_lossy(&out.stderr));
+    out.stdout
+}
+
+fn fails(dir: &Path, args: &[&str]) -> String {
+    let out = run(dir, args, None);
+    assert!(!out.status.success(), "{:?} succeeded", args);
+    String::from_utf8_lossy(&out.stderr).into_owned()
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("silent-threshold-cli-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_protocol() {
+    let dir = temp_dir("protocol");
+    let d = dir.as_path();
+    let params = &["--params", "params.bin"][..];
+    let keys = &["--params", "params.bin", "--agg-key", "agg.bin"][..];
+
+    ok(d, &["setup", "--degree", "4", "-o", "params.bin"]);
+    for id in 1..4 {
+        let (sk, pk) = (format!("sk{}.bin", id), format!("pk{}.hex", id));
+        let id = id.to_string();
+        ok(d, &[&["keygen", "--id", &id, "--n", "4", "--sk-out", &sk, "--pk-out", &pk, "--hex"], params].concat());
+    }
+    fails(d, &[&["keygen", "--id", "0", "--n", "4", "--sk-out", "x", "--pk-out", "y"], params].concat());
+    fails(d, &[&["keygen", "--id", "1", "--n", "3", "--sk-out", "x", "--pk-out", "y"], params].concat());
+    fails(d, &[&["keygen", "--id", "1", "--n", "2", "--sk-out", "x", "--pk-out", "y"], params].concat());
+
+    // public keys in any order, hex or binary
+    ok(d, &[&["aggregate", "-o", "agg.bin", "pk3.hex", "pk1.hex", "pk2.hex"], params].concat());
+    assert!(fails(d, &[&["aggregate", "-o", "x", "pk1.hex", "pk3.hex"], params].concat()).contains("slot 2"));
+
+    let inspect = String::from_utf8(ok(d, &["inspect", "agg.bin"])).unwrap();
+    assert!(inspect.starts_with("aggregate key\n") && inspect.contains("parties: 4"));
+    ok(d, &[&["verify", "agg.bin"], params].concat());
+    assert!(fails(d, &["verify", "agg.bin"]).contains("needs --params"));
+
+    // encrypt from a pipe
+    let plaintext = b"launch codes".to_vec();
+    let out = run(d, &[&["encrypt", "--t", "2", "--label", "ops", "--proof", "-o", "file.enc"], keys].concat(), Some(&plaintext));
+    assert!(out.status.success());
+    let inspect = String::from_utf8(ok(d, &["inspect", "file.enc"])).unwrap();
+    assert!(inspect.contains("threshold: 2") && inspect.contains("well-formedness proof: yes"));
+    assert!(!inspect.contains("warning"));
+    ok(d, &[&["verify", "file.enc"], keys].concat());
+
+    for id in 1..4 {
+        let (sk, part) = (format!("sk{}.bin", id), format!("part{}.bin", id));
+        ok(d, &[&["partial-decrypt", "--sk", &sk, "-o", &part, "file.enc"], keys].concat());
+    }
+    ok(d, &[&["verify", "--ciphertext", "file.enc", "part2.bin"], keys].concat());
+    let partial = &["verify", "--ciphertext", "file.enc", "part2.bin", "--agg-key", "agg.bin"][..];
+    assert!(fails(d, partial).contains("needs --params"));
+
+    // the partial still verifies against the ciphertext, but the envelope's label does not
+    let mut relabeled = fs::read(d.join("file.enc")).unwrap();
+    let at = relabeled.windows(3).position(|w| w == b"ops").unwrap();
+    relabeled[at + 2] = b'x';
+    fs::write(d.join("relabeled.enc"), relabeled).unwrap();
+    let out = fails(d, &[&["verify", "--ciphertext", "relabeled.enc", "part2.bin"], keys].concat());
+    assert!(out.contains("does not verify against its label"));
+
+    // any two parties decrypt, one is not enough, and a partial for another slot is skipped
+    assert_eq!(ok(d, &[&["combine", "file.enc", "part1.bin", "part3.bin"], keys].concat()), plaintext);
+    assert!(fails(d, &[&["combine", "file.enc", "part2.bin"], keys].concat()).contains("threshold is 2"));
+
+    let mut forged = fs::read(d.join("part1.bin")).unwrap();
+    forged[0] = 2;
+    fs::write(d.join("forged.bin"), forged).unwrap();
+    let out = run(d, &[&["combine", "file.enc", "forged.bin", "part3.bin", "part1.bin"], keys].concat(), None);
+    assert!(out.status.success());
+    assert_eq!(out.stdout, plaintext);
+    assert!(String::from_utf8_lossy(&out.stderr).contains("party 2 does not verify"));
+
+    // a modified payload does not decrypt
+    let mut tampered = fs::read(d.join("file.enc")).unwrap();
+    *tampered.last_mut().unwrap() ^= 1;
+    fs::write(d.join("tampered.enc"), tampered).unwrap();
+    fails(d, &[&["combine", "tampered.enc", "part1.bin", "part2.bin"], keys].concat());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_rejects_garbage() {
+    let dir = temp_dir("garbage");
+    fs::write(dir.join("junk"), b"not an object").unwrap();
+    assert!(fails(&dir, &["inspect", "junk"]).contains("not an object"));
+    fs::remove_dir_all(&dir).unwrap();
+}
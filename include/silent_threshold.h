@@ -0,0 +1,167 @@
+#ifndef SILENT_THRESHOLD_H
+#define SILENT_THRESHOLD_H
+
+/* Generated by cbindgen from src/ffi.rs. Do not edit; run SILENT_THRESHOLD_BLESS=1 cargo test --test ffi */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef enum StStatus {
+  ST_STATUS_OK = 0,
+  // A required pointer argument was NULL.
+  ST_STATUS_NULL_POINTER,
+  // Input bytes are not a valid object of the expected type.
+  ST_STATUS_INVALID_ENCODING,
+  // The output buffer is NULL or too small; the required size was written to its length.
+  ST_STATUS_BUFFER_TOO_SMALL,
+  // An argument is out of range, e.g. a slot id or threshold.
+  ST_STATUS_INVALID_ARGUMENT,
+  // A proof, label or partial decryption did not verify.
+  ST_STATUS_VERIFICATION_FAILED,
+  // The objects were built for different params or committees.
+  ST_STATUS_MISMATCH,
+  // Fewer valid partial decryptions than the ciphertext's threshold.
+  ST_STATUS_THRESHOLD_NOT_MET,
+  // A bug in the library; the operation was abandoned.
+  ST_STATUS_PANIC,
+} StStatus;
+
+typedef struct StAggregateKey StAggregateKey;
+
+typedef struct StCiphertext StCiphertext;
+
+typedef struct StParams StParams;
+
+typedef struct StPublicKey StPublicKey;
+
+typedef struct StSecretKey StSecretKey;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+enum StStatus st_params_from_bytes(const uint8_t *bytes, size_t len, struct StParams **out);
+
+enum StStatus st_params_to_bytes(const struct StParams *params, uint8_t *out, size_t *out_len);
+
+// the largest committee size the params support
+enum StStatus st_params_degree(const struct StParams *params, size_t *degree);
+
+void st_params_free(struct StParams *params);
+
+// a fresh secret key from the operating system's randomness
+enum StStatus st_secret_key_generate(struct StSecretKey **out);
+
+enum StStatus st_secret_key_from_bytes(const uint8_t *bytes, size_t len, struct StSecretKey **out);
+
+// writes the 32 byte secret scalar, for the caller to store safely
+enum StStatus st_secret_key_export_secret_bytes(const struct StSecretKey *sk,
+                                                uint8_t *out,
+                                                size_t *out_len);
+
+// the public key and hints of sk for slot id in a committee of n slots, a power of two
+// equal to the degree of params
+enum StStatus st_secret_key_public_key(const struct StSecretKey *sk,
+                                       const struct StParams *params,
+                                       size_t id,
+                                       size_t n,
+                                       struct StPublicKey **out);
+
+// writes the partial decryption of ct, the canonical encoding of a G2 element.
+// ct must verify against label and agg_key, as in st_ciphertext_verify
+enum StStatus st_partial_decryption(const struct StSecretKey *sk,
+                                    const struct StCiphertext *ct,
+                                    const uint8_t *label,
+                                    size_t label_len,
+                                    const struct StAggregateKey *agg_key,
+                                    const struct StParams *params,
+                                    uint8_t *out,
+                                    size_t *out_len);
+
+void st_secret_key_free(struct StSecretKey *sk);
+
+enum StStatus st_public_key_from_bytes(const uint8_t *bytes, size_t len, struct StPublicKey **out);
+
+enum StStatus st_public_key_to_bytes(const struct StPublicKey *pk, uint8_t *out, size_t *out_len);
+
+enum StStatus st_public_key_id(const struct StPublicKey *pk, size_t *id);
+
+// checks a partial decryption of ct against the public key of the party that sent it
+enum StStatus st_verify_partial_decryption(const struct StPublicKey *pk,
+                                           const struct StCiphertext *ct,
+                                           const uint8_t *partial,
+                                           size_t len);
+
+void st_public_key_free(struct StPublicKey *pk);
+
+// aggregates the public keys of slots 1..len, in any order, together with the dummy party
+enum StStatus st_aggregate_key_new(const struct StPublicKey *const *pks,
+                                   size_t len,
+                                   const struct StParams *params,
+                                   struct StAggregateKey **out);
+
+// reads the public keys written by st_aggregate_key_to_bytes and aggregates them again
+// over params, with the same checks as st_aggregate_key_new
+enum StStatus st_aggregate_key_from_bytes(const uint8_t *bytes,
+                                          size_t len,
+                                          const struct StParams *params,
+                                          struct StAggregateKey **out);
+
+enum StStatus st_aggregate_key_to_bytes(const struct StAggregateKey *agg_key,
+                                        uint8_t *out,
+                                        size_t *out_len);
+
+// the number of slots, including the dummy party
+enum StStatus st_aggregate_key_size(const struct StAggregateKey *agg_key, size_t *size);
+
+void st_aggregate_key_free(struct StAggregateKey *agg_key);
+
+// encrypts to the committee with threshold t and label as associated data. the
+// encapsulated key is written to key, and is not part of the ciphertext's encoding.
+// ST_STATUS_MISMATCH is returned if agg_key was built for other params
+enum StStatus st_encrypt(const struct StAggregateKey *agg_key,
+                         const struct StParams *params,
+                         size_t t,
+                         const uint8_t *label,
+                         size_t label_len,
+                         struct StCiphertext **out,
+                         uint8_t *key,
+                         size_t *key_len);
+
+enum StStatus st_ciphertext_from_bytes(const uint8_t *bytes, size_t len, struct StCiphertext **out);
+
+enum StStatus st_ciphertext_to_bytes(const struct StCiphertext *ct, uint8_t *out, size_t *out_len);
+
+enum StStatus st_ciphertext_threshold(const struct StCiphertext *ct, size_t *t);
+
+// checks that ct was encrypted to agg_key under params with this label.
+// parties should run this before releasing a partial decryption
+enum StStatus st_ciphertext_verify(const struct StCiphertext *ct,
+                                   const uint8_t *label,
+                                   size_t label_len,
+                                   const struct StAggregateKey *agg_key,
+                                   const struct StParams *params);
+
+// recovers the encapsulated key of ct. partials[i] is the partial decryption of slot i
+// (partial_lens[i] bytes), or NULL if that party did not answer; slot 0 is ignored, and
+// ST_STATUS_MISMATCH is returned if slot 0 of agg_key is not the dummy party.
+// every given partial is parsed and must verify, and the first t are used
+enum StStatus st_combine(const struct StAggregateKey *agg_key,
+                         const struct StParams *params,
+                         const struct StCiphertext *ct,
+                         const uint8_t *const *partials,
+                         const size_t *partial_lens,
+                         size_t len,
+                         uint8_t *key,
+                         size_t *key_len);
+
+void st_ciphertext_free(struct StCiphertext *ct);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* SILENT_THRESHOLD_H */
@@ -1,124 +1,241 @@
 /* tslint:disable */
 /* eslint-disable */
-/**
-* @param {number} size
-* @returns {any}
-*/
-export function setup_wasm(size: number): any;
-/**
-* @param {any} params
-* @returns {any}
-*/
-export function generate_keys_wasm(params: any): any;
-/**
-* @param {any} agg_key
-* @param {number} t
-* @param {any} params
-* @returns {any}
-*/
-export function encrypt_wasm(agg_key: any, t: number, params: any): any;
-/**
-* @param {any} partial_decryptions
-* @param {any} ct
-* @param {any} selector
-* @param {any} agg_key
-* @param {any} params
-* @returns {any}
-*/
-export function decrypt_wasm(partial_decryptions: any, ct: any, selector: any, agg_key: any, params: any): any;
-/**
-*/
-export function main(): void;
-/**
-*/
+
+export class AggregateKeyHandle {
+    private constructor();
+    free(): void;
+    [Symbol.dispose](): void;
+    /**
+     * recovers the encapsulated key of ct. partials[i] is party i's partial decryption
+     * as a Uint8Array, or null/undefined if it did not respond; missing trailing entries
+     * count as absent
+     */
+    combine(partials: any[], ct: Ciphertext): Uint8Array;
+    /**
+     * parses the agg_key returned by generate_keys_wasm, sharing params with the handle
+     */
+    static from_bytes(bytes: Uint8Array, params: Params): AggregateKeyHandle;
+    to_bytes(): Uint8Array;
+    /**
+     * stable hash of the committee, as recorded in ciphertexts and keystores
+     */
+    readonly fingerprint: Uint8Array;
+    /**
+     * number of parties, including the dummy party 0
+     */
+    readonly size: number;
+}
+
+export class Ciphertext {
+    private constructor();
+    free(): void;
+    [Symbol.dispose](): void;
+    /**
+     * parses a ciphertext returned by encrypt_wasm or to_bytes
+     */
+    static from_bytes(bytes: Uint8Array): Ciphertext;
+    to_bytes(): Uint8Array;
+    readonly threshold: number;
+}
+
 export class CiphertextWrapper {
-  free(): void;
-/**
-*/
-  data: Uint8Array;
+    private constructor();
+    free(): void;
+    [Symbol.dispose](): void;
+    static from_bytes(bytes: Uint8Array): CiphertextWrapper;
+    to_bytes(): Uint8Array;
+    readonly data: Uint8Array;
 }
-/**
-*/
+
+export class Params {
+    free(): void;
+    [Symbol.dispose](): void;
+    /**
+     * encrypts to agg_key with threshold t. agg_key must have been read with these params
+     */
+    encrypt(agg_key: AggregateKeyHandle, t: number): Ciphertext;
+    /**
+     * parses and checks params returned by setup_wasm or to_bytes
+     */
+    static from_bytes(bytes: Uint8Array): Params;
+    /**
+     * runs the setup for a committee of size parties, like setup_wasm
+     */
+    constructor(size: number);
+    to_bytes(): Uint8Array;
+    readonly degree: number;
+}
+
 export class PublicKeyWrapper {
-  free(): void;
-/**
-*/
-  data: Uint8Array;
+    private constructor();
+    free(): void;
+    [Symbol.dispose](): void;
+    static from_bytes(bytes: Uint8Array): PublicKeyWrapper;
+    to_bytes(): Uint8Array;
+    readonly data: Uint8Array;
 }
-/**
-*/
+
 export class SecretKeyWrapper {
-  free(): void;
+    private constructor();
+    free(): void;
+    [Symbol.dispose](): void;
+    /**
+     * encrypts the key under passphrase into a JSON keystore for party slot. committee is
+     * AggregateKeyHandle.fingerprint, if the committee has been formed
+     */
+    export_encrypted(passphrase: string, slot: number, committee?: Uint8Array | null): string;
+    /**
+     * the secret scalar bytes that from_bytes reads. the copy handed to JS is not wiped
+     */
+    export_secret_bytes(): Uint8Array;
+    static from_bytes(bytes: Uint8Array): SecretKeyWrapper;
+    /**
+     * a fresh secret key drawn from crypto.getRandomValues, for a party that keeps its own key
+     */
+    static generate(): SecretKeyWrapper;
+    /**
+     * decrypts a keystore written by export_encrypted; keystore_info reads its slot and committee
+     */
+    static import_encrypted(keystore: string, passphrase: string): SecretKeyWrapper;
+    /**
+     * partial decryption of a ciphertext handle, in the format combine expects.
+     * the ciphertext must verify against agg_key and label (empty for Params.encrypt)
+     */
+    partial_decryption(ct: Ciphertext, agg_key: AggregateKeyHandle, label?: Uint8Array | null): Uint8Array;
+    /**
+     * partial decryption of a ciphertext in the canonical encoding, as G2 point bytes.
+     * the ciphertext must verify against agg_key and label (empty for encrypt_wasm)
+     */
+    partial_decryption_js(ct: Uint8Array, agg_key: Uint8Array, params: Uint8Array, label?: Uint8Array | null): Uint8Array;
+    /**
+     * the public key bytes of party slot, in 1..params.degree, to pass on for aggregation
+     */
+    public_key(params: Uint8Array, slot: number): Uint8Array;
+}
+
+/**
+ * decrypt_wasm, reporting the "parse", "prepare" and "combine" phases
+ */
+export function decrypt_async(partial_decryptions: Uint8Array[], ct: Uint8Array, selector: any, agg_key: Uint8Array, params: Uint8Array, progress?: Function | null, signal?: AbortSignal | null): Promise<Uint8Array>;
+
+export function decrypt_wasm(partial_decryptions: any, ct: Uint8Array, selector: any, agg_key: Uint8Array, params: Uint8Array): Uint8Array;
+
+export function encrypt_wasm(agg_key: Uint8Array, t: number, params: Uint8Array): Uint8Array;
+
+/**
+ * generate_keys_wasm, reporting the "parse", "keygen" (one chunk per party) and "aggregate" phases
+ */
+export function generate_keys_async(params: Uint8Array, progress?: Function | null, signal?: AbortSignal | null): Promise<any>;
+
 /**
-* @returns {number}
-*/
-  to_secret_key(): number;
+ * returns { sk: Uint8Array[], agg_key: Uint8Array } for a committee of params.degree parties.
+ * sk[0] is the dummy party's key, which is 1
+ */
+export function generate_keys_wasm(params: Uint8Array): any;
+
 /**
-* @param {any} js_value
-* @returns {SecretKeyWrapper}
-*/
-  static from_js_value(js_value: any): SecretKeyWrapper;
+ * installs the console subscriber for spans and events up to level
+ * ("error", "warn", "info", "debug" or "trace", default "info").
+ * Builds with the no-logging feature have nothing to report.
+ */
+export function init_logging(level?: string | null): void;
+
 /**
-* @returns {any}
-*/
-  to_js_value(): any;
+ * returns { curve, slot, committee: Uint8Array | undefined } of a keystore, without the passphrase.
+ * this is not authenticated, anyone can edit it; only a successful import_encrypted vouches for it
+ */
+export function keystore_info(keystore: string): any;
+
+export function main(): void;
+
 /**
-* @param {any} ct
-* @returns {any}
-*/
-  partial_decryption_js(ct: any): any;
+ * setup_wasm, reporting the "setup" phase
+ */
+export function setup_async(size: number, progress?: Function | null, signal?: AbortSignal | null): Promise<Uint8Array>;
+
 /**
-*/
-  data: Uint8Array;
-}
+ * params for a committee of size parties, size a power of two
+ */
+export function setup_wasm(size: number): Uint8Array;
 
 export type InitInput = RequestInfo | URL | Response | BufferSource | WebAssembly.Module;
 
 export interface InitOutput {
-  readonly memory: WebAssembly.Memory;
-  readonly secretkeywrapper_to_secret_key: (a: number) => number;
-  readonly secretkeywrapper_from_js_value: (a: number) => number;
-  readonly secretkeywrapper_to_js_value: (a: number) => number;
-  readonly secretkeywrapper_partial_decryption_js: (a: number, b: number) => number;
-  readonly __wbg_ciphertextwrapper_free: (a: number) => void;
-  readonly ciphertextwrapper_data: (a: number, b: number) => void;
-  readonly ciphertextwrapper_set_data: (a: number, b: number, c: number) => void;
-  readonly secretkeywrapper_data: (a: number, b: number) => void;
-  readonly publickeywrapper_data: (a: number, b: number) => void;
-  readonly __wbg_secretkeywrapper_free: (a: number) => void;
-  readonly __wbg_publickeywrapper_free: (a: number) => void;
-  readonly secretkeywrapper_set_data: (a: number, b: number, c: number) => void;
-  readonly publickeywrapper_set_data: (a: number, b: number, c: number) => void;
-  readonly setup_wasm: (a: number) => number;
-  readonly generate_keys_wasm: (a: number) => number;
-  readonly encrypt_wasm: (a: number, b: number, c: number) => number;
-  readonly decrypt_wasm: (a: number, b: number, c: number, d: number, e: number) => number;
-  readonly main: () => void;
-  readonly __wbindgen_malloc: (a: number, b: number) => number;
-  readonly __wbindgen_realloc: (a: number, b: number, c: number, d: number) => number;
-  readonly __wbindgen_add_to_stack_pointer: (a: number) => number;
-  readonly __wbindgen_free: (a: number, b: number, c: number) => void;
-  readonly __wbindgen_exn_store: (a: number) => void;
-  readonly __wbindgen_start: () => void;
+    readonly memory: WebAssembly.Memory;
+    readonly __wbg_aggregatekeyhandle_free: (a: number, b: number) => void;
+    readonly __wbg_ciphertext_free: (a: number, b: number) => void;
+    readonly __wbg_ciphertextwrapper_free: (a: number, b: number) => void;
+    readonly __wbg_params_free: (a: number, b: number) => void;
+    readonly __wbg_publickeywrapper_free: (a: number, b: number) => void;
+    readonly __wbg_secretkeywrapper_free: (a: number, b: number) => void;
+    readonly aggregatekeyhandle_combine: (a: number, b: number, c: number, d: number) => [number, number, number, number];
+    readonly aggregatekeyhandle_fingerprint: (a: number) => [number, number];
+    readonly aggregatekeyhandle_from_bytes: (a: number, b: number, c: number) => [number, number, number];
+    readonly aggregatekeyhandle_size: (a: number) => number;
+    readonly aggregatekeyhandle_to_bytes: (a: number) => [number, number];
+    readonly ciphertext_from_bytes: (a: number, b: number) => [number, number, number];
+    readonly ciphertext_threshold: (a: number) => number;
+    readonly ciphertext_to_bytes: (a: number) => [number, number];
+    readonly ciphertextwrapper_data: (a: number) => [number, number];
+    readonly ciphertextwrapper_from_bytes: (a: number, b: number) => [number, number, number];
+    readonly ciphertextwrapper_to_bytes: (a: number) => [number, number];
+    readonly decrypt_async: (a: number, b: number, c: number, d: number, e: any, f: number, g: number, h: number, i: number, j: number, k: number) => any;
+    readonly decrypt_wasm: (a: any, b: number, c: number, d: any, e: number, f: number, g: number, h: number) => [number, number, number, number];
+    readonly encrypt_wasm: (a: number, b: number, c: number, d: number, e: number) => [number, number, number, number];
+    readonly generate_keys_async: (a: number, b: number, c: number, d: number) => any;
+    readonly generate_keys_wasm: (a: number, b: number) => [number, number, number];
+    readonly init_logging: (a: number, b: number) => [number, number];
+    readonly keystore_info: (a: number, b: number) => [number, number, number];
+    readonly main: () => void;
+    readonly params_degree: (a: number) => number;
+    readonly params_encrypt: (a: number, b: number, c: number) => [number, number, number];
+    readonly params_from_bytes: (a: number, b: number) => [number, number, number];
+    readonly params_new: (a: number) => [number, number, number];
+    readonly params_to_bytes: (a: number) => [number, number];
+    readonly publickeywrapper_data: (a: number) => [number, number];
+    readonly publickeywrapper_from_bytes: (a: number, b: number) => [number, number, number];
+    readonly publickeywrapper_to_bytes: (a: number) => [number, number];
+    readonly secretkeywrapper_export_encrypted: (a: number, b: number, c: number, d: number, e: number, f: number) => [number, number, number, number];
+    readonly secretkeywrapper_export_secret_bytes: (a: number) => [number, number];
+    readonly secretkeywrapper_from_bytes: (a: number, b: number) => [number, number, number];
+    readonly secretkeywrapper_generate: () => number;
+    readonly secretkeywrapper_import_encrypted: (a: number, b: number, c: number, d: number) => [number, number, number];
+    readonly secretkeywrapper_partial_decryption: (a: number, b: number, c: number, d: number, e: number) => [number, number, number, number];
+    readonly secretkeywrapper_partial_decryption_js: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number) => [number, number, number, number];
+    readonly secretkeywrapper_public_key: (a: number, b: number, c: number, d: number) => [number, number, number, number];
+    readonly setup_async: (a: number, b: number, c: number) => any;
+    readonly setup_wasm: (a: number) => [number, number, number, number];
+    readonly wasm_bindgen_85b648d803070ec___convert__closures_____invoke___js_sys_26dee340ea3a8b96___Function_fn_wasm_bindgen_85b648d803070ec___JsValue_____wasm_bindgen_85b648d803070ec___sys__Undefined___js_sys_26dee340ea3a8b96___Function_fn_wasm_bindgen_85b648d803070ec___JsValue_____wasm_bindgen_85b648d803070ec___sys__Undefined_______true_: (a: number, b: number, c: any, d: any) => void;
+    readonly wasm_bindgen_85b648d803070ec___convert__closures_____invoke___wasm_bindgen_85b648d803070ec___JsValue__core_a377585fb64a80ef___result__Result_____wasm_bindgen_85b648d803070ec___JsError___true_: (a: number, b: number, c: any) => [number, number];
+    readonly __wbindgen_malloc: (a: number, b: number) => number;
+    readonly __wbindgen_realloc: (a: number, b: number, c: number, d: number) => number;
+    readonly __wbindgen_exn_store: (a: number) => void;
+    readonly __externref_table_alloc: () => number;
+    readonly __wbindgen_externrefs: WebAssembly.Table;
+    readonly __wbindgen_free: (a: number, b: number, c: number) => void;
+    readonly __wbindgen_destroy_closure: (a: number, b: number) => void;
+    readonly __externref_table_dealloc: (a: number) => void;
+    readonly __wbindgen_start: () => void;
 }
 
 export type SyncInitInput = BufferSource | WebAssembly.Module;
+
 /**
-* Instantiates the given `module`, which can either be bytes or
-* a precompiled `WebAssembly.Module`.
-*
-* @param {SyncInitInput} module
-*
-* @returns {InitOutput}
-*/
-export function initSync(module: SyncInitInput): InitOutput;
+ * Instantiates the given `module`, which can either be bytes or
+ * a precompiled `WebAssembly.Module`.
+ *
+ * @param {{ module: SyncInitInput }} module - Passing `SyncInitInput` directly is deprecated.
+ *
+ * @returns {InitOutput}
+ */
+export function initSync(module: { module: SyncInitInput } | SyncInitInput): InitOutput;
 
 /**
-* If `module_or_path` is {RequestInfo} or {URL}, makes a request and
-* for everything else, calls `WebAssembly.instantiate` directly.
-*
-* @param {InitInput | Promise<InitInput>} module_or_path
-*
-* @returns {Promise<InitOutput>}
-*/
-export default function __wbg_init (module_or_path?: InitInput | Promise<InitInput>): Promise<InitOutput>;
+ * If `module_or_path` is {RequestInfo} or {URL}, makes a request and
+ * for everything else, calls `WebAssembly.instantiate` directly.
+ *
+ * @param {{ module_or_path: InitInput | Promise<InitInput> }} module_or_path - Passing `InitInput` directly is deprecated.
+ *
+ * @returns {Promise<InitOutput>}
+ */
+export default function __wbg_init (module_or_path?: { module_or_path: InitInput | Promise<InitInput> } | InitInput | Promise<InitInput>): Promise<InitOutput>;
@@ -1,27 +1,57 @@
 /* tslint:disable */
 /* eslint-disable */
 export const memory: WebAssembly.Memory;
-export function secretkeywrapper_to_secret_key(a: number): number;
-export function secretkeywrapper_from_js_value(a: number): number;
-export function secretkeywrapper_to_js_value(a: number): number;
-export function secretkeywrapper_partial_decryption_js(a: number, b: number): number;
-export function __wbg_ciphertextwrapper_free(a: number): void;
-export function ciphertextwrapper_data(a: number, b: number): void;
-export function ciphertextwrapper_set_data(a: number, b: number, c: number): void;
-export function secretkeywrapper_data(a: number, b: number): void;
-export function publickeywrapper_data(a: number, b: number): void;
-export function __wbg_secretkeywrapper_free(a: number): void;
-export function __wbg_publickeywrapper_free(a: number): void;
-export function secretkeywrapper_set_data(a: number, b: number, c: number): void;
-export function publickeywrapper_set_data(a: number, b: number, c: number): void;
-export function setup_wasm(a: number): number;
-export function generate_keys_wasm(a: number): number;
-export function encrypt_wasm(a: number, b: number, c: number): number;
-export function decrypt_wasm(a: number, b: number, c: number, d: number, e: number): number;
-export function main(): void;
-export function __wbindgen_malloc(a: number, b: number): number;
-export function __wbindgen_realloc(a: number, b: number, c: number, d: number): number;
-export function __wbindgen_add_to_stack_pointer(a: number): number;
-export function __wbindgen_free(a: number, b: number, c: number): void;
-export function __wbindgen_exn_store(a: number): void;
-export function __wbindgen_start(): void;
+export const __wbg_aggregatekeyhandle_free: (a: number, b: number) => void;
+export const __wbg_ciphertext_free: (a: number, b: number) => void;
+export const __wbg_ciphertextwrapper_free: (a: number, b: number) => void;
+export const __wbg_params_free: (a: number, b: number) => void;
+export const __wbg_publickeywrapper_free: (a: number, b: number) => void;
+export const __wbg_secretkeywrapper_free: (a: number, b: number) => void;
+export const aggregatekeyhandle_combine: (a: number, b: number, c: number, d: number) => [number, number, number, number];
+export const aggregatekeyhandle_fingerprint: (a: number) => [number, number];
+export const aggregatekeyhandle_from_bytes: (a: number, b: number, c: number) => [number, number, number];
+export const aggregatekeyhandle_size: (a: number) => number;
+export const aggregatekeyhandle_to_bytes: (a: number) => [number, number];
+export const ciphertext_from_bytes: (a: number, b: number) => [number, number, number];
+export const ciphertext_threshold: (a: number) => number;
+export const ciphertext_to_bytes: (a: number) => [number, number];
+export const ciphertextwrapper_data: (a: number) => [number, number];
+export const ciphertextwrapper_from_bytes: (a: number, b: number) => [number, number, number];
+export const ciphertextwrapper_to_bytes: (a: number) => [number, number];
+export const decrypt_async: (a: number, b: number, c: number, d: number, e: any, f: number, g: number, h: number, i: number, j: number, k: number) => any;
+export const decrypt_wasm: (a: any, b: number, c: number, d: any, e: number, f: number, g: number, h: number) => [number, number, number, number];
+export const encrypt_wasm: (a: number, b: number, c: number, d: number, e: number) => [number, number, number, number];
+export const generate_keys_async: (a: number, b: number, c: number, d: number) => any;
+export const generate_keys_wasm: (a: number, b: number) => [number, number, number];
+export const init_logging: (a: number, b: number) => [number, number];
+export const keystore_info: (a: number, b: number) => [number, number, number];
+export const main: () => void;
+export const params_degree: (a: number) => number;
+export const params_encrypt: (a: number, b: number, c: number) => [number, number, number];
+export const params_from_bytes: (a: number, b: number) => [number, number, number];
+export const params_new: (a: number) => [number, number, number];
+export const params_to_bytes: (a: number) => [number, number];
+export const publickeywrapper_data: (a: number) => [number, number];
+export const publickeywrapper_from_bytes: (a: number, b: number) => [number, number, number];
+export const publickeywrapper_to_bytes: (a: number) => [number, number];
+export const secretkeywrapper_export_encrypted: (a: number, b: number, c: number, d: number, e: number, f: number) => [number, number, number, number];
+export const secretkeywrapper_export_secret_bytes: (a: number) => [number, number];
+export const secretkeywrapper_from_bytes: (a: number, b: number) => [number, number, number];
+export const secretkeywrapper_generate: () => number;
+export const secretkeywrapper_import_encrypted: (a: number, b: number, c: number, d: number) => [number, number, number];
+export const secretkeywrapper_partial_decryption: (a: number, b: number, c: number, d: number, e: number) => [number, number, number, number];
+export const secretkeywrapper_partial_decryption_js: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number) => [number, number, number, number];
+export const secretkeywrapper_public_key: (a: number, b: number, c: number, d: number) => [number, number, number, number];
+export const setup_async: (a: number, b: number, c: number) => any;
+export const setup_wasm: (a: number) => [number, number, number, number];
+export const wasm_bindgen_85b648d803070ec___convert__closures_____invoke___js_sys_26dee340ea3a8b96___Function_fn_wasm_bindgen_85b648d803070ec___JsValue_____wasm_bindgen_85b648d803070ec___sys__Undefined___js_sys_26dee340ea3a8b96___Function_fn_wasm_bindgen_85b648d803070ec___JsValue_____wasm_bindgen_85b648d803070ec___sys__Undefined_______true_: (a: number, b: number, c: any, d: any) => void;
+export const wasm_bindgen_85b648d803070ec___convert__closures_____invoke___wasm_bindgen_85b648d803070ec___JsValue__core_a377585fb64a80ef___result__Result_____wasm_bindgen_85b648d803070ec___JsError___true_: (a: number, b: number, c: any) => [number, number];
+export const __wbindgen_malloc: (a: number, b: number) => number;
+export const __wbindgen_realloc: (a: number, b: number, c: number, d: number) => number;
+export const __wbindgen_exn_store: (a: number) => void;
+export const __externref_table_alloc: () => number;
+export const __wbindgen_externrefs: WebAssembly.Table;
+export const __wbindgen_free: (a: number, b: number, c: number) => void;
+export const __wbindgen_destroy_closure: (a: number, b: number) => void;
+export const __externref_table_dealloc: (a: number) => void;
+export const __wbindgen_start: () => void;
@@ -0,0 +1,166 @@
+use ark_bls12_381::{g2, Bls12_381};
+use ark_ec::hashing::{
+    curve_maps::wb::WBMap, map_to_curve_hasher::MapToCurveBasedHasher, HashToCurve,
+};
+use ark_ec::{pairing::Pairing, Group};
+use ark_ff::field_hashers::DefaultFieldHasher;
+use ark_std::Zero;
+use sha2::Sha256;
+
+use crate::setup::{PublicKey, SecretKey};
+
+/// domain separation tag for proofs of possession, from the IETF BLS signature draft
+pub const POP_DST: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// domain separation tag for signatures, from the IETF BLS signature draft (proof of possession scheme)
+pub const SIG_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// Pairings whose G2 supports hashing arbitrary messages to the curve.
+/// Public keys live in G1, so messages are hashed into G2.
+pub trait HashToG2: Pairing {
+    fn hash_to_g2(msg: &[u8], dst: &[u8]) -> Self::G2;
+}
+
+/// BLS12381G2_XMD:SHA-256_SSWU_RO_ from RFC 9380
+impl HashToG2 for Bls12_381 {
+    fn hash_to_g2(msg: &[u8], dst: &[u8]) -> Self::G2 {
+        let hasher = MapToCurveBasedHasher::<
+            Self::G2,
+            DefaultFieldHasher<Sha256, 128>,
+            WBMap<g2::Config>,
+        >::new(dst)
+        .expect("valid hash to curve parameters");
+        hasher
+            .hash(msg)
+            .expect("hashing to G2 does not fail")
+            .into()
+    }
+}
+
+impl<E: HashToG2> SecretKey<E> {
+    /// BLS signature on msg under the same key used for partial decryptions.
+    /// partial_decryption only answers ciphertexts carrying a proof of knowledge of gamma,
+    /// so it cannot be used to obtain signatures on chosen hash points
+    pub fn sign(&self, msg: &[u8]) -> E::G2 {
+        E::hash_to_g2(msg, SIG_DST) * self.scalar()
+    }
+}
+
+impl<E: HashToG2> PublicKey<E> {
+    /// checks a BLS signature on msg against bls_pk
+    pub fn verify(&self, msg: &[u8], sig: &E::G2) -> bool {
+        let hm = E::hash_to_g2(msg, SIG_DST);
+        E::multi_pairing([-E::G1::generator(), self.bls_pk], [*sig, hm]).is_zero()
+    }
+}
+
+/// sums signatures into a single aggregate signature
+pub fn aggregate_signatures<E: Pairing>(sigs: &[E::G2]) -> E::G2 {
+    sigs.iter().sum()
+}
+
+/// checks an aggregate signature where pk[i] signed msgs[i].
+/// this does not check proofs of possession: messages need not be distinct only if the
+/// caller made sure every key passed verify_possession, e.g. by taking pk from an AggregateKey
+pub fn aggregate_verify<E: HashToG2>(pk: &[PublicKey<E>], msgs: &[&[u8]], sig: &E::G2) -> bool {
+    if pk.is_empty() || pk.len() != msgs.len() {
+        return false;
+    }
+
+    let mut lhs = vec![-E::G1::generator()];
+    let mut rhs = vec![*sig];
+    for (pki, msg) in pk.iter().zip(msgs.iter()) {
+        lhs.push(pki.bls_pk);
+        rhs.push(E::hash_to_g2(msg, SIG_DST));
+    }
+    E::multi_pairing(lhs, rhs).is_zero()
+}
+
+/// checks an aggregate signature where every key in pk signed the same msg
+pub fn fast_aggregate_verify<E: HashToG2>(pk: &[PublicKey<E>], msg: &[u8], sig: &E::G2) -> bool {
+    if pk.is_empty() {
+        return false;
+    }
+
+    let apk: E::G1 = pk.iter().map(|pki| pki.bls_pk).sum();
+    let hm = E::hash_to_g2(msg, SIG_DST);
+    E::multi_pairing([-E::G1::generator(), apk], [*sig, hm]).is_zero()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::{Fq, Fq2, G2Affine};
+    use ark_ff::PrimeField;
+
+    fn fq(hex: &str) -> Fq {
+        let hex = hex.trim_start_matches("0x");
+        let bytes: Vec<u8> = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect();
+        Fq::from_be_bytes_mod_order(&bytes)
+    }
+
+    #[test]
+    fn test_hash_to_g2_vector() {
+        // RFC 9380, appendix J.10.1, msg = ""
+        let dst = b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+        let expected = G2Affine::new(
+            Fq2::new(
+                fq("0x0141ebfbdca40eb85b87142e130ab689c673cf60f1a3e98d69335266f30d9b8d4ac44c1038e9dcdd5393faf5c41fb78a"),
+                fq("0x05cb8437535e20ecffaef7752baddf98034139c38452458baeefab379ba13dff5bf5dd71b72418717047f5b0f37da03d"),
+            ),
+            Fq2::new(
+                fq("0x0503921d7f6a12805e72940b963c0cf3471c7b2a524950ca195d11062ee75ec076daf2d4bc358c4b190c0c98064fdd92"),
+                fq("0x12424ac32561493f3fe3c260708a12b7c620e7be00099a974e259ddc7d1f6395c3c811cdd19f1e8dbf3e9ecfdcbab8d6"),
+            ),
+        );
+
+        let p: G2Affine = Bls12_381::hash_to_g2(b"", dst).into();
+        assert_eq!(p, expected);
+    }
+
+    #[test]
+    fn test_sign_and_aggregate() {
+        type E = Bls12_381;
+        type UniPoly381 = ark_poly::univariate::DensePolynomial<<E as Pairing>::ScalarField>;
+
+        let mut rng = ark_std::test_rng();
+        let n = 4;
+        let params = crate::kzg::KZG10::<E, UniPoly381>::setup(n, &mut rng).unwrap();
+
+        let sk: Vec<SecretKey<E>> = (0..n).map(|_| SecretKey::<E>::new(&mut rng)).collect();
+        let pk: Vec<PublicKey<E>> = (0..n).map(|i| sk[i].get_pk(i, &params, n)).collect();
+
+        let sig = sk[0].sign(b"manifest");
+        assert!(pk[0].verify(b"manifest", &sig));
+        assert!(!pk[0].verify(b"other", &sig));
+        assert!(!pk[1].verify(b"manifest", &sig));
+
+        // everyone approves the same manifest
+        let sigs: Vec<_> = sk.iter().map(|ski| ski.sign(b"manifest")).collect();
+        let agg = aggregate_signatures::<E>(&sigs);
+        assert!(fast_aggregate_verify(&pk, b"manifest", &agg));
+        assert!(!fast_aggregate_verify(&pk[1..], b"manifest", &agg));
+
+        // everyone signs their own message
+        let msgs: Vec<Vec<u8>> = (0..n).map(|i| format!("partial {}", i).into_bytes()).collect();
+        let msgs: Vec<&[u8]> = msgs.iter().map(|m| m.as_slice()).collect();
+        let sigs: Vec<_> = sk.iter().zip(msgs.iter()).map(|(ski, m)| ski.sign(m)).collect();
+        let agg = aggregate_signatures::<E>(&sigs);
+        assert!(aggregate_verify(&pk, &msgs, &agg));
+        assert!(!aggregate_verify(&pk, &[msgs[1], msgs[0], msgs[2], msgs[3]], &agg));
+
+        // partial decryption is not a signing oracle: a ciphertext whose gamma_g2 is H(m)
+        // has no valid proof of knowledge of gamma and gets no answer
+        let agg_key = crate::setup::AggregateKey::new(pk, &params).unwrap();
+        let mut forged = crate::encryption::encrypt(&agg_key, 1, &params, &mut rng);
+        assert!(sk[1].partial_decryption(&forged, &[], &agg_key, &params).is_ok());
+        forged.gamma_g2 = E::hash_to_g2(b"manifest", SIG_DST);
+        assert!(matches!(
+            sk[1].partial_decryption(&forged, &[], &agg_key, &params),
+            Err(crate::setup::Error::InvalidCiphertext)
+        ));
+    }
+}
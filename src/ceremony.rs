@@ -0,0 +1,242 @@
+//! Updatable powers-of-tau ceremony producing UniversalParams.
+//!
+//! Every contributor multiplies the i-th powers by x^i for a secret x and
+//! publishes an UpdateProof. The resulting tau is the product of all the x's,
+//! so the params are safe as long as one contributor discarded their x.
+
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::{One, UniformRand, Zero};
+use ark_serialize::*;
+use ark_std::rand::RngCore;
+
+use crate::{kzg::UniversalParams, utils::hash_to_field};
+
+const UPDATE_PROOF_DST: &[u8] = b"SILENT-THRESHOLD-V01-CS01-CEREMONY-UPDATE";
+
+/// Proof that a contributor moved tau_g1 from the previous value by a secret factor x
+#[derive(CanonicalSerialize, CanonicalDeserialize, Clone)]
+pub struct UpdateProof<E: Pairing> {
+    /// g^{tau} after this update
+    pub tau_g1: E::G1Affine,
+    /// h^x, ties the update to the previous tau through a pairing
+    pub x_g2: E::G2Affine,
+    /// Schnorr proof of knowledge of x for x_g2
+    pub c: E::ScalarField,
+    pub z: E::ScalarField,
+}
+
+/// The current params together with every update applied so far
+#[derive(CanonicalSerialize, CanonicalDeserialize)]
+pub struct Ceremony<E: Pairing> {
+    pub params: UniversalParams<E>,
+    pub contributions: Vec<UpdateProof<E>>,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    /// The degree provided was too small; degree 0 params are not supported.
+    DegreeIsZero,
+
+    /// A contribution does not prove knowledge of its update factor
+    /// or does not extend the previous tau.
+    InvalidUpdate {
+        /// The position of the offending contribution in the transcript.
+        index: usize,
+    },
+
+    /// The transcript has no contributions, so tau is still the publicly known 1.
+    NoContributions,
+
+    /// The final params do not match the last contribution,
+    /// are not powers of a single tau, or are powers of tau = 1.
+    InconsistentParams,
+}
+
+impl<E: Pairing> Ceremony<E> {
+    /// Starts a ceremony at tau = 1, so every power is the generator.
+    /// No contributor has to be trusted at this point.
+    pub fn new(max_degree: usize) -> Result<Self, Error> {
+        if max_degree < 1 {
+            return Err(Error::DegreeIsZero);
+        }
+
+        let params = UniversalParams::new(
+            vec![E::G1Affine::generator(); max_degree + 1],
+            vec![E::G2Affine::generator(); max_degree + 1],
+        );
+
+        Ok(Ceremony {
+            params,
+            contributions: Vec::new(),
+        })
+    }
+
+    /// Re-randomizes the params with a fresh secret factor and records the proof.
+    /// The factor is dropped when this returns.
+    pub fn contribute<R: RngCore>(&mut self, rng: &mut R) -> &UpdateProof<E> {
+        let mut x = E::ScalarField::rand(rng);
+        while x.is_zero() {
+            x = E::ScalarField::rand(rng);
+        }
+
+        let prev_tau_g1 = self.params.powers_of_g[1];
+        self.params = update_params(&self.params, x);
+
+        let h = E::G2Affine::generator();
+        let x_g2 = (h * x).into_affine();
+        let r = E::ScalarField::rand(rng);
+        let r_g2 = h * r;
+        let index = self.contributions.len();
+        let tau_g1 = self.params.powers_of_g[1];
+        let c = update_challenge::<E>(index, &prev_tau_g1, &tau_g1, &x_g2, &r_g2);
+
+        self.contributions.push(UpdateProof {
+            tau_g1,
+            x_g2,
+            c,
+            z: r + c * x,
+        });
+        self.contributions.last().unwrap()
+    }
+
+    /// Checks the whole transcript offline: every update proves knowledge of its
+    /// factor and extends the previous tau, and the final params are powers of the last tau.
+    /// A transcript needs at least one contribution: the starting params have a known trapdoor.
+    pub fn verify(&self) -> Result<(), Error> {
+        if self.contributions.is_empty() {
+            return Err(Error::NoContributions);
+        }
+        let h = E::G2Affine::generator();
+        let mut prev_tau_g1 = E::G1Affine::generator();
+
+        for (index, proof) in self.contributions.iter().enumerate() {
+            // r_g2 = z*h - c*x_g2
+            let r_g2 = (h * proof.z) - (proof.x_g2 * proof.c);
+            let c = update_challenge::<E>(index, &prev_tau_g1, &proof.tau_g1, &proof.x_g2, &r_g2);
+            if c != proof.c || proof.tau_g1.is_zero() {
+                return Err(Error::InvalidUpdate { index });
+            }
+
+            // e(tau_g1, h) = e(prev_tau_g1, x_g2)
+            let extends_prev = E::multi_pairing(
+                [proof.tau_g1.into_group(), -prev_tau_g1.into_group()],
+                [h, proof.x_g2],
+            )
+            .is_zero();
+            if !extends_prev {
+                return Err(Error::InvalidUpdate { index });
+            }
+
+            prev_tau_g1 = proof.tau_g1;
+        }
+
+        // updates multiplying to 1 would bring back the starting trapdoor
+        if self.params.powers_of_g.len() < 2
+            || self.params.powers_of_g[1] != prev_tau_g1
+            || prev_tau_g1 == E::G1Affine::generator()
+        {
+            return Err(Error::InconsistentParams);
+        }
+        if self.params.check().is_err() {
+            return Err(Error::InconsistentParams);
+        }
+
+        Ok(())
+    }
+}
+
+/// multiplies the i-th powers by x^i, turning powers of tau into powers of x*tau
+pub fn update_params<E: Pairing>(params: &UniversalParams<E>, x: E::ScalarField) -> UniversalParams<E> {
+    let len = params.powers_of_g.len().max(params.powers_of_h.len());
+    let mut powers_of_x = Vec::with_capacity(len);
+    let mut cur = E::ScalarField::one();
+    for _ in 0..len {
+        powers_of_x.push(cur);
+        cur *= x;
+    }
+
+    let powers_of_g: Vec<E::G1> = params
+        .powers_of_g
+        .iter()
+        .zip(powers_of_x.iter())
+        .map(|(g, x)| *g * x)
+        .collect();
+    let powers_of_h: Vec<E::G2> = params
+        .powers_of_h
+        .iter()
+        .zip(powers_of_x.iter())
+        .map(|(h, x)| *h * x)
+        .collect();
+
+    UniversalParams::new(E::G1::normalize_batch(&powers_of_g), E::G2::normalize_batch(&powers_of_h))
+}
+
+fn update_challenge<E: Pairing>(
+    index: usize,
+    prev_tau_g1: &E::G1Affine,
+    tau_g1: &E::G1Affine,
+    x_g2: &E::G2Affine,
+    r_g2: &E::G2,
+) -> E::ScalarField {
+    let mut msg = Vec::new();
+    index.serialize_compressed(&mut msg).unwrap();
+    prev_tau_g1.serialize_compressed(&mut msg).unwrap();
+    tau_g1.serialize_compressed(&mut msg).unwrap();
+    x_g2.serialize_compressed(&mut msg).unwrap();
+    r_g2.serialize_compressed(&mut msg).unwrap();
+    hash_to_field(UPDATE_PROOF_DST, &msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kzg::KZG10;
+    use ark_poly::{univariate::DensePolynomial, DenseUVPolynomial};
+
+    type E = ark_bls12_381::Bls12_381;
+    type UniPoly381 = DensePolynomial<<E as Pairing>::ScalarField>;
+
+    #[test]
+    fn test_ceremony() {
+        let mut rng = ark_std::test_rng();
+        let n = 8;
+
+        // the starting params have tau = 1, which everyone knows
+        let mut ceremony = Ceremony::<E>::new(n).unwrap();
+        assert!(matches!(ceremony.verify(), Err(Error::NoContributions)));
+        for _ in 0..3 {
+            ceremony.contribute(&mut rng);
+        }
+        assert!(ceremony.verify().is_ok());
+
+        // the transcript survives a round trip and verifies offline
+        let mut bytes = Vec::new();
+        ceremony.serialize_compressed(&mut bytes).unwrap();
+        let ceremony = Ceremony::<E>::deserialize_compressed(&bytes[..]).unwrap();
+        assert!(ceremony.verify().is_ok());
+
+        // the result is usable like any other params
+        let poly = DensePolynomial::from_coefficients_vec(vec![1u64.into(), 2u64.into()]);
+        let _ = KZG10::<E, UniPoly381>::commit_g1(&ceremony.params, &poly).unwrap();
+
+        // a contributor that overwrites tau with a value of their choosing is caught
+        let mut forged = Ceremony::<E>::new(n).unwrap();
+        forged.contributions = ceremony.contributions.clone();
+        forged.params = KZG10::<E, UniPoly381>::setup(n, &mut rng).unwrap();
+        assert!(matches!(forged.verify(), Err(Error::InconsistentParams)));
+
+        let mut forged = Ceremony::<E>::new(n).unwrap();
+        forged.params = update_params(&ceremony.params, 2u64.into());
+        forged.contributions = ceremony.contributions.clone();
+        let mut proof = forged.contributions[2].clone();
+        proof.tau_g1 = forged.params.powers_of_g[1];
+        forged.contributions[2] = proof;
+        assert!(matches!(forged.verify(), Err(Error::InvalidUpdate { index: 2 })));
+
+        // params whose G1 and G2 powers disagree are rejected
+        let mut broken = Ceremony::<E>::new(n).unwrap();
+        broken.contribute(&mut rng);
+        broken.params.powers_of_h[3] = broken.params.powers_of_h[2];
+        assert!(matches!(broken.verify(), Err(Error::InconsistentParams)));
+    }
+}
@@ -1,11 +1,13 @@
 use ark_ec::{
     pairing::{Pairing, PairingOutput},
-    VariableBaseMSM,
+    CurveGroup, VariableBaseMSM,
 };
 use ark_poly::{
     univariate::DensePolynomial, DenseUVPolynomial, EvaluationDomain, Polynomial,
     Radix2EvaluationDomain,
 };
+use ark_ff::Field;
+use ark_serialize::CanonicalSerialize;
 use ark_std::{One, Zero};
 use std::ops::Div;
 
@@ -13,36 +15,308 @@ use crate::{
     encryption::Ciphertext,
     kzg::{UniversalParams, KZG10},
     setup::AggregateKey,
-    utils::interp_mostly_zero,
+    utils::{hash_to_field, interp_mostly_zero_on_domain},
 };
 
+const BATCH_DST: &[u8] = b"SILENT-THRESHOLD-V01-CS01-DECRYPTION-BATCH";
+
+#[derive(Debug)]
+pub enum Error {
+    /// The ciphertext, aggregate key and params were built for different degrees.
+    DegreeMismatch {
+        /// The degree recorded in the ciphertext.
+        ciphertext: usize,
+        /// The degree recorded in the aggregate key.
+        agg_key: usize,
+        /// The degree of the params passed in.
+        params: usize,
+    },
+
+    /// The ciphertext was created with different params.
+    ParamsMismatch,
+
+    /// The ciphertext was encrypted to a different aggregate key.
+    AggregateKeyMismatch,
+
+    /// The partials or the selector do not have one entry per slot of the committee.
+    LengthMismatch {
+        /// The committee size.
+        n: usize,
+        /// The number of partials passed in.
+        partials: usize,
+        /// The length of the selector.
+        selector: usize,
+    },
+
+    /// The selector leaves out the dummy party 0, which is always part of the aggregate.
+    DummyNotSelected,
+
+    /// Fewer than t slots, counting the dummy party, are selected.
+    ThresholdNotMet {
+        /// The number of selected slots, including the dummy party.
+        selected: usize,
+        /// The threshold.
+        t: usize,
+    },
+
+    /// The aggregated hints or partial decryptions do not verify: a selected partial
+    /// decryption is wrong, or the aggregate key's hints are inconsistent.
+    InvalidAggregate,
+}
+
+/// recovers the key encapsulated in ct, through a DecryptionContext used once.
+/// the partial decryptions must already be verified, e.g. with PublicKey::verify_partial_decryption;
+/// a wrong one gives a wrong key. agg_dec_checked also accepts unverified partials
 pub fn agg_dec<E: Pairing>(
     partial_decryptions: &[E::G2], //insert 0 if a party did not respond or verification failed
     ct: &Ciphertext<E>,
     selector: &[bool],
     agg_key: &AggregateKey<E>,
     params: &UniversalParams<E>,
+) -> Result<PairingOutput<E>, Error> {
+    let ctx = DecryptionContext::new(agg_key, params);
+    let prepared = ctx.prepare(ct)?;
+    let enc_key = ctx.agg_dec(partial_decryptions, &prepared, selector)?;
+
+    debug_assert_eq!(enc_key, ct.enc_key);
+
+    Ok(enc_key)
+}
+
+/// same as agg_dec, but checks the aggregate first, so that a wrong partial decryption is
+/// an error instead of a wrong key
+pub fn agg_dec_checked<E: Pairing>(
+    partial_decryptions: &[E::G2],
+    ct: &Ciphertext<E>,
+    selector: &[bool],
+    agg_key: &AggregateKey<E>,
+    params: &UniversalParams<E>,
+) -> Result<PairingOutput<E>, Error> {
+    let ctx = DecryptionContext::new(agg_key, params);
+    let prepared = ctx.prepare(ct)?;
+    ctx.agg_dec_checked(partial_decryptions, &prepared, selector)
+}
+
+/// Everything agg_dec derives from the committee and params alone, computed once and
+/// reused across ciphertexts: the G2 elements that are fixed pairing inputs (h, h^tau and
+/// z_g2), prepared for the Miller loop.
+///
+/// Decapsulation pairs the hints only with sa2, whose G2 elements the encryptor randomized,
+/// so the fixed elements serve the opt-in agg_dec_checked and
+/// ThresholdSignature::verify_with_context. What decryption saves per ciphertext is the
+/// prepared sa2 of a PreparedCiphertext, reused when the same ciphertext is combined again.
+pub struct DecryptionContext<'a, E: Pairing> {
+    pub agg_key: &'a AggregateKey<E>,
+    pub params: &'a UniversalParams<E>,
+    pub(crate) h: E::G2Prepared,
+    pub(crate) h_tau: E::G2Prepared,
+    pub(crate) z_g2: E::G2Prepared,
+}
+
+/// A ciphertext checked against a DecryptionContext, with its G2 elements prepared
+pub struct PreparedCiphertext<'c, E: Pairing> {
+    pub ct: &'c Ciphertext<E>,
+    sa2: [E::G2Prepared; 6],
+    gamma_g2: E::G2Prepared,
+}
+
+impl<'a, E: Pairing> DecryptionContext<'a, E> {
+    #[tracing::instrument(name = "decryption_context", level = "debug", skip_all, fields(n = agg_key.pk.len()))]
+    pub fn new(agg_key: &'a AggregateKey<E>, params: &'a UniversalParams<E>) -> Self {
+        DecryptionContext {
+            agg_key,
+            params,
+            h: E::G2Prepared::from(params.powers_of_h[0]),
+            h_tau: E::G2Prepared::from(params.powers_of_h[1]),
+            z_g2: E::G2Prepared::from(agg_key.z_g2),
+        }
+    }
+
+    /// checks that ct was encrypted to this committee and prepares it for agg_dec
+    pub fn prepare<'c>(&self, ct: &'c Ciphertext<E>) -> Result<PreparedCiphertext<'c, E>, Error> {
+        // decrypting with the wrong params or committee would silently give a wrong key
+        check_binding(ct, self.agg_key, self.params)?;
+        Ok(PreparedCiphertext {
+            ct,
+            sa2: ct.sa2.map(E::G2Prepared::from),
+            gamma_g2: E::G2Prepared::from(ct.gamma_g2),
+        })
+    }
+
+    /// same as the agg_dec function, reusing the context and a ciphertext prepared by it
+    #[tracing::instrument(name = "decrypt", skip_all, fields(t = ct.ct.t, n = self.agg_key.pk.len()))]
+    pub fn agg_dec(
+        &self,
+        partial_decryptions: &[E::G2],
+        ct: &PreparedCiphertext<E>,
+        selector: &[bool],
+    ) -> Result<PairingOutput<E>, Error> {
+        let hints = self.aggregate(partial_decryptions, ct, selector)?;
+        Ok(self.decapsulate(&hints, ct))
+    }
+
+    /// same as the agg_dec function, with check_aggregate before decapsulating: one more
+    /// multi-Miller loop over the prepared fixed elements, for partials nobody verified
+    #[tracing::instrument(name = "decrypt_checked", skip_all, fields(t = ct.ct.t, n = self.agg_key.pk.len()))]
+    pub fn agg_dec_checked(
+        &self,
+        partial_decryptions: &[E::G2],
+        ct: &PreparedCiphertext<E>,
+        selector: &[bool],
+    ) -> Result<PairingOutput<E>, Error> {
+        let hints = self.aggregate(partial_decryptions, ct, selector)?;
+        self.check_aggregate(&hints, ct)?;
+        Ok(self.decapsulate(&hints, ct))
+    }
+
+    /// the first step of agg_dec_checked, split out so that decrypt_async can yield between steps
+    pub(crate) fn aggregate(
+        &self,
+        partial_decryptions: &[E::G2],
+        ct: &PreparedCiphertext<E>,
+        selector: &[bool],
+    ) -> Result<HintAggregate<E>, Error> {
+        aggregate_hints(partial_decryptions, ct.ct.t, selector, self.agg_key, self.params)
+    }
+
+    /// the last step of agg_dec_checked; gives a wrong key if a selected partial is wrong
+    pub(crate) fn decapsulate(&self, hints: &HintAggregate<E>, ct: &PreparedCiphertext<E>) -> PairingOutput<E> {
+        decapsulate(hints, &ct.ct.sa1, &ct.sa2)
+    }
+
+    /// The checks ThresholdSignature::verify makes on the hints, with the BLS check on the
+    /// partial decryptions in place of the one on partial signatures, batched with
+    /// Fiat-Shamir powers of r:
+    /// 1. e(ask, B) = e(qz, z) e(qx, h^tau) e(apk, h)
+    /// 2. e(qx, h^tau) = e(qhatx, h)
+    /// 3. e(apk, gamma_g2) = e(g, sigma)
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) fn check_aggregate(&self, hints: &HintAggregate<E>, ct: &PreparedCiphertext<E>) -> Result<(), Error> {
+        let mut bytes = Vec::new();
+        for g1 in [hints.apk, hints.qx, hints.qz, hints.qhatx] {
+            g1.serialize_compressed(&mut bytes).unwrap();
+        }
+        for g2 in [hints.sigma, hints.b_g2, ct.ct.gamma_g2] {
+            g2.serialize_compressed(&mut bytes).unwrap();
+        }
+        let r1: E::ScalarField = hash_to_field(BATCH_DST, &bytes);
+        let r2 = r1.square();
+        let r3 = r2 * r1;
+
+        let g: E::G1 = self.params.powers_of_g[0].into();
+        let lhs = [
+            self.agg_key.ask * r1,
+            -(hints.qz * r1),
+            hints.qx * (r2 - r1),
+            -(hints.apk * r1) - (hints.qhatx * r2),
+            hints.apk * r3,
+            -(g * r3),
+        ];
+        let rhs = [
+            E::G2Prepared::from(hints.b_g2),
+            self.z_g2.clone(),
+            self.h_tau.clone(),
+            self.h.clone(),
+            ct.gamma_g2.clone(),
+            E::G2Prepared::from(hints.sigma),
+        ];
+
+        if E::final_exponentiation(E::multi_miller_loop(lhs, rhs)).unwrap().is_zero() {
+            Ok(())
+        } else {
+            Err(Error::InvalidAggregate)
+        }
+    }
+}
+
+fn check_binding<E: Pairing>(
+    ct: &Ciphertext<E>,
+    agg_key: &AggregateKey<E>,
+    params: &UniversalParams<E>,
+) -> Result<(), Error> {
+    if ct.degree != params.degree() || agg_key.degree != params.degree() {
+        return Err(Error::DegreeMismatch {
+            ciphertext: ct.degree,
+            agg_key: agg_key.degree,
+            params: params.degree(),
+        });
+    }
+    if ct.params_fingerprint != params.fingerprint() {
+        return Err(Error::ParamsMismatch);
+    }
+    if ct.agg_key_fingerprint != agg_key.fingerprint() {
+        return Err(Error::AggregateKeyMismatch);
+    }
+    Ok(())
+}
+
+/// recovers the encapsulated key as e(w1||sa1, sa2||w2)
+#[tracing::instrument(level = "debug", skip_all)]
+fn decapsulate<E: Pairing>(
+    hints: &HintAggregate<E>,
+    sa1: &[E::G1; 2],
+    sa2: &[E::G2Prepared; 6],
 ) -> PairingOutput<E> {
+    let w1 = [-hints.apk, -hints.qz, -hints.qx, hints.qhatx, -hints.bhat_g1, -hints.q0_g1];
+    let w2 = [hints.b_g2, hints.sigma].map(E::G2Prepared::from);
+
+    let lhs = w1.into_iter().chain(sa1.iter().copied());
+    let rhs = sa2.iter().cloned().chain(w2);
+    E::final_exponentiation(E::multi_miller_loop(lhs, rhs)).unwrap()
+}
+
+/// The aggregate public key of the parties picked by selector together with the
+/// hint openings and commitments to B proving that at least t of them (besides
+/// the dummy party) are included. Shared by decryption and threshold signatures.
+pub(crate) struct HintAggregate<E: Pairing> {
+    pub apk: E::G1,
+    /// partials (decryptions or signatures) aggregated with the same weights as apk
+    pub sigma: E::G2,
+    pub qx: E::G1,
+    pub qz: E::G1,
+    pub qhatx: E::G1,
+    pub b_g2: E::G2,
+    pub q0_g1: E::G1,
+    pub bhat_g1: E::G1,
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub(crate) fn aggregate_hints<E: Pairing>(
+    partials: &[E::G2], //insert 0 if a party did not respond or verification failed
+    t: usize,
+    selector: &[bool],
+    agg_key: &AggregateKey<E>,
+    params: &UniversalParams<E>,
+) -> Result<HintAggregate<E>, Error> {
     let n = agg_key.pk.len();
+    if partials.len() != n || selector.len() != n {
+        return Err(Error::LengthMismatch {
+            n,
+            partials: partials.len(),
+            selector: selector.len(),
+        });
+    }
+    // B(omega^0) = 1 is what makes the hints sound; B is never zero at the dummy party
+    if !selector[0] {
+        return Err(Error::DummyNotSelected);
+    }
     let domain = Radix2EvaluationDomain::<E::ScalarField>::new(n).unwrap();
     let domain_elements: Vec<E::ScalarField> = domain.elements().collect();
 
-    // points is where B is set to zero
-    // parties is the set of parties who have signed
-    let mut points = vec![domain_elements[0]]; // 0 is the dummy party that is always true
-    let mut parties: Vec<usize> = Vec::new(); // parties indexed from 0..n-1
-    for i in 0..n {
-        if selector[i] {
-            parties.push(i);
-        } else {
-            points.push(domain_elements[i]);
-        }
+    // absent is where B is set to zero
+    let absent: Vec<usize> = (0..n).filter(|&i| !selector[i]).collect(); // 0 is the dummy party that is always true
+    // x^t * B must fit in the params, i.e. deg B = |absent| <= degree - t
+    if t + absent.len() > params.degree() {
+        return Err(Error::ThresholdNotMet {
+            selected: n - absent.len(),
+            t,
+        });
     }
 
-    let b = interp_mostly_zero(E::ScalarField::one(), &points);
+    let b = interp_mostly_zero_on_domain(E::ScalarField::one(), &domain, &absent);
     let b_evals = domain.fft(&b.coeffs);
 
-    debug_assert!(b.degree() == points.len() - 1);
+    debug_assert!(b.degree() == absent.len());
     debug_assert!(b.evaluate(&domain_elements[0]) == E::ScalarField::one());
 
     // commit to b in g2
@@ -66,10 +340,10 @@ pub fn agg_dec<E: Pairing>(
 
     // bhat = x^t * b
     // insert t 0s at the beginning of bhat.coeffs
-    let mut bhat_coeffs = vec![E::ScalarField::zero(); ct.t];
+    let mut bhat_coeffs = vec![E::ScalarField::zero(); t];
     bhat_coeffs.append(&mut b.coeffs.clone());
     let bhat = DensePolynomial::from_coefficients_vec(bhat_coeffs);
-    debug_assert_eq!(bhat.degree(), n - 1);
+    debug_assert!(bhat.degree() <= params.degree());
 
     let bhat_g1: E::G1 = KZG10::<E, DensePolynomial<E::ScalarField>>::commit_g1(params, &bhat)
         .unwrap()
@@ -77,74 +351,36 @@ pub fn agg_dec<E: Pairing>(
 
     let n_inv = E::ScalarField::one() / E::ScalarField::from((n) as u32);
 
-    // compute the aggregate public key
-    let mut bases: Vec<<E as Pairing>::G1Affine> = Vec::new();
-    let mut scalars: Vec<<E as Pairing>::ScalarField> = Vec::new();
-    for &i in &parties {
-        bases.push(agg_key.pk[i].bls_pk.into());
-        scalars.push(b_evals[i]);
-    }
-    let mut apk = E::G1::msm(bases.as_slice(), scalars.as_slice()).unwrap();
-    apk *= n_inv;
-
-    // compute sigma = (\sum B(omega^i)partial_decryptions[i])/(n) for i in parties
-    let mut bases: Vec<<E as Pairing>::G2Affine> = Vec::new();
-    let mut scalars: Vec<<E as Pairing>::ScalarField> = Vec::new();
-    for &i in &parties {
-        bases.push(partial_decryptions[i].into());
-        scalars.push(b_evals[i]);
-    }
-    let mut sigma = E::G2::msm(bases.as_slice(), scalars.as_slice()).unwrap();
+    // the hint aggregates all share the scalars B(omega^i); absent parties contribute nothing
+    let scalars: Vec<E::ScalarField> = b_evals
+        .iter()
+        .zip(selector.iter())
+        .map(|(&b, &selected)| if selected { b } else { E::ScalarField::zero() })
+        .collect();
+
+    // apk, Qx, Qz and Qhatx over the precomputed affine hints
+    let bases = &agg_key.bases;
+    let msm = |bases: &[E::G1Affine]| E::G1::msm(bases, &scalars).unwrap();
+    let apk = msm(&bases.bls_pk) * n_inv;
+    let qx = msm(&bases.sk_li_by_tau);
+    let qz = msm(&bases.agg_sk_li_by_z);
+    let qhatx = msm(&bases.sk_li_minus0);
+
+    // sigma = (\sum B(omega^i)partials[i])/(n); partials change on every call, so normalize them in one batch
+    let partials = E::G2::normalize_batch(partials);
+    let mut sigma = E::G2::msm(&partials, &scalars).unwrap();
     sigma *= n_inv;
 
-    // compute Qx, Qhatx and Qz
-    let mut bases: Vec<<E as Pairing>::G1Affine> = Vec::new();
-    let mut scalars: Vec<<E as Pairing>::ScalarField> = Vec::new();
-    for &i in &parties {
-        bases.push(agg_key.pk[i].sk_li_by_tau.into());
-        scalars.push(b_evals[i]);
-    }
-    let qx = E::G1::msm(bases.as_slice(), scalars.as_slice()).unwrap();
-
-    let mut bases: Vec<<E as Pairing>::G1Affine> = Vec::new();
-    let mut scalars: Vec<<E as Pairing>::ScalarField> = Vec::new();
-    for &i in &parties {
-        bases.push(agg_key.agg_sk_li_by_z[i].into());
-        scalars.push(b_evals[i]);
-    }
-    let qz = E::G1::msm(bases.as_slice(), scalars.as_slice()).unwrap();
-
-    let mut bases: Vec<<E as Pairing>::G1Affine> = Vec::new();
-    let mut scalars: Vec<<E as Pairing>::ScalarField> = Vec::new();
-    for &i in &parties {
-        bases.push(agg_key.pk[i].sk_li_minus0.into());
-        scalars.push(b_evals[i]);
-    }
-    let qhatx = E::G1::msm(bases.as_slice(), scalars.as_slice()).unwrap();
-
-    // e(w1||sa1, sa2||w2)
-    let minus1 = -E::ScalarField::one();
-    let w1 = [
-        apk * (minus1),
-        qz * (minus1),
-        qx * (minus1),
+    Ok(HintAggregate {
+        apk,
+        sigma,
+        qx,
+        qz,
         qhatx,
-        bhat_g1 * (minus1),
-        q0_g1 * (minus1),
-    ];
-    let w2 = [b_g2, sigma];
-
-    let mut enc_key_lhs = w1.to_vec();
-    enc_key_lhs.append(&mut ct.sa1.to_vec());
-
-    let mut enc_key_rhs = ct.sa2.to_vec();
-    enc_key_rhs.append(&mut w2.to_vec());
-
-    let enc_key = E::multi_pairing(enc_key_lhs, enc_key_rhs);
-
-    debug_assert_eq!(enc_key, ct.enc_key);
-
-    enc_key
+        b_g2,
+        q0_g1,
+        bhat_g1,
+    })
 }
 
 #[cfg(test)]
@@ -162,6 +398,7 @@ mod tests {
     type UniPoly381 = DensePolynomial<<E as Pairing>::ScalarField>;
 
     #[test]
+    #[allow(clippy::needless_range_loop, clippy::same_item_push)]
     fn test_decryption() {
         let mut rng = ark_std::test_rng();
         let n = 1 << 4; // actually n-1 total parties. one party is a dummy party that is always true
@@ -183,18 +420,22 @@ mod tests {
             pk.push(sk[i].get_pk(i, &params, n))
         }
 
-        let agg_key = AggregateKey::<E>::new(pk, &params);
-        let ct = encrypt::<E>(&agg_key, t, &params);
+        let agg_key = AggregateKey::<E>::new(pk, &params).unwrap();
+        let ct = encrypt::<E, _>(&agg_key, t, &params, &mut rng);
 
         // compute partial decryptions
         let mut partial_decryptions: Vec<G2> = Vec::new();
         for i in 0..t + 1 {
-            partial_decryptions.push(sk[i].partial_decryption(&ct));
+            partial_decryptions.push(sk[i].partial_decryption(&ct, &[], &agg_key, &params).unwrap());
         }
         for _ in t + 1..n {
             partial_decryptions.push(G2::zero());
         }
 
+        // partials can be checked against the public keys before combining
+        assert!(agg_key.pk[1].verify_partial_decryption(&ct, &partial_decryptions[1]));
+        assert!(!agg_key.pk[2].verify_partial_decryption(&ct, &partial_decryptions[1]));
+
         // compute the decryption key
         let mut selector: Vec<bool> = Vec::new();
         for _ in 0..t + 1 {
@@ -204,6 +445,89 @@ mod tests {
             selector.push(false);
         }
 
-        let _dec_key = agg_dec(&partial_decryptions, &ct, &selector, &agg_key, &params);
+        let dec_key = agg_dec(&partial_decryptions, &ct, &selector, &agg_key, &params).unwrap();
+        assert_eq!(dec_key, ct.enc_key);
+
+        assert_eq!(agg_dec_checked(&partial_decryptions, &ct, &selector, &agg_key, &params).unwrap(), ct.enc_key);
+
+        // a wrong partial decryption gives a wrong key, unless the aggregate is checked
+        let mut bad = partial_decryptions.clone();
+        bad[1] = partial_decryptions[2];
+        let ctx = DecryptionContext::new(&agg_key, &params);
+        let prepared = ctx.prepare(&ct).unwrap();
+        assert_ne!(ctx.agg_dec(&bad, &prepared, &selector).unwrap(), ct.enc_key);
+        assert!(matches!(
+            agg_dec_checked(&bad, &ct, &selector, &agg_key, &params),
+            Err(Error::InvalidAggregate)
+        ));
+
+        // the dummy party is always selected
+        let mut no_dummy = selector.clone();
+        no_dummy[0] = false;
+        assert!(matches!(
+            agg_dec(&partial_decryptions, &ct, &no_dummy, &agg_key, &params),
+            Err(Error::DummyNotSelected)
+        ));
+
+        // one partial and one selector entry per slot
+        assert!(matches!(
+            agg_dec(&partial_decryptions[..n - 1], &ct, &selector, &agg_key, &params),
+            Err(Error::LengthMismatch { partials: 15, selector: 16, .. })
+        ));
+        assert!(matches!(
+            agg_dec(&partial_decryptions, &ct, &selector[..t + 1], &agg_key, &params),
+            Err(Error::LengthMismatch { partials: 16, selector: 9, .. })
+        ));
+
+        // the context path agrees, and a prepared ciphertext can be combined again
+        // with a different set of parties
+        let ctx = DecryptionContext::new(&agg_key, &params);
+        let prepared = ctx.prepare(&ct).unwrap();
+        assert_eq!(ctx.agg_dec(&partial_decryptions, &prepared, &selector).unwrap(), ct.enc_key);
+
+        let mut others = partial_decryptions.clone();
+        let mut other_selector = selector.clone();
+        others[1] = G2::zero();
+        other_selector[1] = false;
+        others[n - 1] = sk[n - 1].partial_decryption(&ct, &[], &agg_key, &params).unwrap();
+        other_selector[n - 1] = true;
+        assert_eq!(ctx.agg_dec(&others, &prepared, &other_selector).unwrap(), ct.enc_key);
+
+        // the same ciphertext against other params or another committee is refused
+        let other_params = KZG10::<E, UniPoly381>::setup(n, &mut rng).unwrap();
+        assert!(matches!(
+            agg_dec(&partial_decryptions, &ct, &selector, &agg_key, &other_params),
+            Err(Error::ParamsMismatch)
+        ));
+
+        let mut other_pk = agg_key.pk.clone();
+        other_pk[1] = SecretKey::<E>::new(&mut rng).get_pk(1, &params, n);
+        let other_key = AggregateKey::<E>::new(other_pk, &params).unwrap();
+        assert!(matches!(
+            agg_dec(&partial_decryptions, &ct, &selector, &other_key, &params),
+            Err(Error::AggregateKeyMismatch)
+        ));
+        assert!(matches!(
+            DecryptionContext::new(&other_key, &params).prepare(&ct),
+            Err(Error::AggregateKeyMismatch)
+        ));
+
+        // or against a larger SRS the params were truncated from
+        let big = KZG10::<E, UniPoly381>::setup(2 * n, &mut rng).unwrap();
+        let params = big.truncate(n).unwrap();
+        let pk: Vec<PublicKey<E>> = sk.iter().enumerate().map(|(i, ski)| ski.get_pk(i, &params, n)).collect();
+        let agg_key = AggregateKey::<E>::new(pk, &params).unwrap();
+        let ct = encrypt::<E, _>(&agg_key, t, &params, &mut rng);
+        let partial_decryptions: Vec<G2> = (0..n)
+            .map(|i| match selector[i] {
+                true => sk[i].partial_decryption(&ct, &[], &agg_key, &params).unwrap(),
+                false => G2::zero(),
+            })
+            .collect();
+        assert!(agg_dec(&partial_decryptions, &ct, &selector, &agg_key, &params).is_ok());
+        assert!(matches!(
+            agg_dec(&partial_decryptions, &ct, &selector, &agg_key, &big),
+            Err(Error::DegreeMismatch { ciphertext: 16, agg_key: 16, params: 32 })
+        ));
     }
 }
@@ -1,12 +1,92 @@
 use std::ops::Mul;
 
-use crate::{kzg::UniversalParams, setup::AggregateKey};
+use crate::{kzg::UniversalParams, setup::AggregateKey, utils::hash_to_field};
 use ark_ec::{
     pairing::{Pairing, PairingOutput},
     Group,
 };
 use ark_serialize::*;
-use ark_std::{UniformRand, Zero};
+use ark_std::{rand::RngCore, UniformRand, Zero};
+
+const LABEL_PROOF_DST: &[u8] = b"SILENT-THRESHOLD-V01-CS01-LABEL-PROOF";
+const WELL_FORMEDNESS_DST: &[u8] = b"SILENT-THRESHOLD-V01-CS01-WELL-FORMEDNESS";
+
+/// The part of the aggregate key and the params that encrypt reads.
+/// This is all that is needed to check a WellFormednessProof.
+#[derive(CanonicalSerialize, CanonicalDeserialize, Clone)]
+pub struct EncryptionKey<E: Pairing> {
+    pub ask: E::G1,
+    pub z_g2: E::G2,
+    pub h_minus1: E::G2,
+    pub e_gh: PairingOutput<E>,
+    /// g^{tau^t} for every threshold t the committee supports
+    pub powers_of_g: Vec<E::G1Affine>,
+    pub h: E::G2Affine,
+    pub h_tau: E::G2Affine,
+    /// fingerprints of the params and aggregate key these bases were taken from
+    pub params_fingerprint: [u8; 32],
+    pub agg_key_fingerprint: [u8; 32],
+}
+
+impl<E: Pairing> EncryptionKey<E> {
+    pub fn new(apk: &AggregateKey<E>, params: &UniversalParams<E>) -> Self {
+        let n = apk.pk.len();
+        let max_t = n.min(params.powers_of_g.len() - 1);
+        EncryptionKey {
+            ask: apk.ask,
+            z_g2: apk.z_g2,
+            h_minus1: apk.h_minus1,
+            e_gh: apk.e_gh,
+            powers_of_g: params.powers_of_g[..=max_t].to_vec(),
+            h: params.powers_of_h[0],
+            h_tau: params.powers_of_h[1],
+            params_fingerprint: params.fingerprint(),
+            agg_key_fingerprint: apk.fingerprint(),
+        }
+    }
+
+    /// The linear map encrypt applies to w = (s0, s1, s2, s3, s4, gamma) for threshold t,
+    /// returning (sa1, [gamma_g2, sa2]). gamma_g2 is taken from the ciphertext as the base for s2.
+    fn encryption_map(
+        &self,
+        t: usize,
+        gamma_g2: &E::G2,
+        w: &[E::ScalarField; 6],
+    ) -> ([E::G1; 2], [E::G2; 7]) {
+        let g = self.powers_of_g[0];
+        let h = self.h;
+        let sa1 = [
+            (self.ask * w[0]) + (self.powers_of_g[t] * w[3]) + (g * w[4]),
+            g * w[2],
+        ];
+        let sa2 = [
+            h * w[5],
+            (h * w[0]) + (*gamma_g2 * w[2]),
+            self.z_g2 * w[0],
+            self.h_tau * (w[0] + w[1]),
+            h * w[1],
+            h * w[3],
+            (self.h_minus1 + self.h_tau) * w[4],
+        ];
+        (sa1, sa2)
+    }
+}
+
+/// Schnorr proof of knowledge of gamma where the challenge is bound to the label,
+/// the remaining ciphertext components and the aggregate key
+#[derive(CanonicalSerialize, CanonicalDeserialize, Clone)]
+pub struct LabelProof<E: Pairing> {
+    pub c: E::ScalarField,
+    pub z: E::ScalarField,
+}
+
+/// Fiat-Shamir proof of knowledge of the encryption randomness (s0, s1, s2, s3, s4, gamma)
+/// such that gamma_g2, sa1 and sa2 are exactly what encrypt computes for the stated t
+#[derive(CanonicalSerialize, CanonicalDeserialize, Clone)]
+pub struct WellFormednessProof<E: Pairing> {
+    pub c: E::ScalarField,
+    pub z: [E::ScalarField; 6],
+}
 
 #[derive(CanonicalSerialize, CanonicalDeserialize, Clone)]
 pub struct Ciphertext<E: Pairing> {
@@ -15,15 +95,26 @@ pub struct Ciphertext<E: Pairing> {
     pub sa2: [E::G2; 6],
     pub enc_key: PairingOutput<E>, //key to be used for encapsulation
     pub t: usize,                  //threshold
+    pub degree: usize,             //degree of the params used to encrypt
+    pub params_fingerprint: [u8; 32],
+    pub agg_key_fingerprint: [u8; 32],
+    pub label_proof: LabelProof<E>,
+    pub wf_proof: Option<WellFormednessProof<E>>,
 }
 
 impl<E: Pairing> Ciphertext<E> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         gamma_g2: E::G2,
         sa1: [E::G1; 2],
         sa2: [E::G2; 6],
         enc_key: PairingOutput<E>,
         t: usize,
+        degree: usize,
+        params_fingerprint: [u8; 32],
+        agg_key_fingerprint: [u8; 32],
+        label_proof: LabelProof<E>,
+        wf_proof: Option<WellFormednessProof<E>>,
     ) -> Self {
         Ciphertext {
             gamma_g2,
@@ -31,18 +122,161 @@ impl<E: Pairing> Ciphertext<E> {
             sa2,
             enc_key,
             t,
+            degree,
+            params_fingerprint,
+            agg_key_fingerprint,
+            label_proof,
+            wf_proof,
         }
     }
+
+    /// checks that the ciphertext was produced for label and agg_key and has not been mauled.
+    /// parties should run this before releasing a partial decryption
+    pub fn verify(
+        &self,
+        label: &[u8],
+        agg_key: &AggregateKey<E>,
+        params: &UniversalParams<E>,
+    ) -> bool {
+        // r_g2 = z*h - c*gamma_g2
+        let r_g2 = (params.powers_of_h[0] * self.label_proof.z) - (self.gamma_g2 * self.label_proof.c);
+        self.label_challenge(label, agg_key, &r_g2) == self.label_proof.c
+    }
+
+    /// Fiat-Shamir challenge over everything in the ciphertext except the proof itself
+    fn label_challenge(
+        &self,
+        label: &[u8],
+        agg_key: &AggregateKey<E>,
+        r_g2: &E::G2,
+    ) -> E::ScalarField {
+        let mut msg = Vec::new();
+        label.serialize_compressed(&mut msg).unwrap();
+        self.gamma_g2.serialize_compressed(&mut msg).unwrap();
+        self.sa1.serialize_compressed(&mut msg).unwrap();
+        self.sa2.serialize_compressed(&mut msg).unwrap();
+        self.t.serialize_compressed(&mut msg).unwrap();
+        self.degree.serialize_compressed(&mut msg).unwrap();
+        self.params_fingerprint.serialize_compressed(&mut msg).unwrap();
+        self.agg_key_fingerprint.serialize_compressed(&mut msg).unwrap();
+        self.wf_proof.serialize_compressed(&mut msg).unwrap();
+        agg_key.ask.serialize_compressed(&mut msg).unwrap();
+        agg_key.z_g2.serialize_compressed(&mut msg).unwrap();
+        r_g2.serialize_compressed(&mut msg).unwrap();
+        hash_to_field(LABEL_PROOF_DST, &msg)
+    }
+
+    /// checks the attached WellFormednessProof against ek.
+    /// returns false if the ciphertext carries no proof, was made for other params or
+    /// another committee, or t is out of range for ek
+    pub fn verify_well_formed(&self, ek: &EncryptionKey<E>) -> bool {
+        let proof = match &self.wf_proof {
+            Some(proof) => proof,
+            None => return false,
+        };
+        if self.t >= ek.powers_of_g.len()
+            || self.params_fingerprint != ek.params_fingerprint
+            || self.agg_key_fingerprint != ek.agg_key_fingerprint
+        {
+            return false;
+        }
+
+        // commitments = map(z) - c*statement
+        let (mut a1, mut a2) = ek.encryption_map(self.t, &self.gamma_g2, &proof.z);
+        let x2 = self.wf_statement_g2();
+        for (a, x) in a1.iter_mut().zip(self.sa1.iter()) {
+            *a -= *x * proof.c;
+        }
+        for (a, x) in a2.iter_mut().zip(x2.iter()) {
+            *a -= *x * proof.c;
+        }
+
+        self.wf_challenge(ek, &a1, &a2) == proof.c
+    }
+
+    /// gamma_g2 followed by sa2, in the order of EncryptionKey::encryption_map
+    fn wf_statement_g2(&self) -> [E::G2; 7] {
+        let mut x2 = [self.gamma_g2; 7];
+        x2[1..].copy_from_slice(&self.sa2);
+        x2
+    }
+
+    /// Fiat-Shamir challenge over the statement (the bases encryption_map uses for t and the
+    /// fingerprints they come from), every ciphertext field the proof covers and the commitments
+    fn wf_challenge(
+        &self,
+        ek: &EncryptionKey<E>,
+        a1: &[E::G1; 2],
+        a2: &[E::G2; 7],
+    ) -> E::ScalarField {
+        let mut msg = Vec::new();
+        ek.params_fingerprint.serialize_compressed(&mut msg).unwrap();
+        ek.agg_key_fingerprint.serialize_compressed(&mut msg).unwrap();
+        ek.powers_of_g[0].serialize_compressed(&mut msg).unwrap();
+        ek.powers_of_g[self.t].serialize_compressed(&mut msg).unwrap();
+        ek.h.serialize_compressed(&mut msg).unwrap();
+        ek.h_tau.serialize_compressed(&mut msg).unwrap();
+        ek.h_minus1.serialize_compressed(&mut msg).unwrap();
+        ek.ask.serialize_compressed(&mut msg).unwrap();
+        ek.z_g2.serialize_compressed(&mut msg).unwrap();
+        self.t.serialize_compressed(&mut msg).unwrap();
+        self.degree.serialize_compressed(&mut msg).unwrap();
+        self.params_fingerprint.serialize_compressed(&mut msg).unwrap();
+        self.agg_key_fingerprint.serialize_compressed(&mut msg).unwrap();
+        self.gamma_g2.serialize_compressed(&mut msg).unwrap();
+        self.sa1.serialize_compressed(&mut msg).unwrap();
+        self.sa2.serialize_compressed(&mut msg).unwrap();
+        a1.serialize_compressed(&mut msg).unwrap();
+        a2.serialize_compressed(&mut msg).unwrap();
+        hash_to_field(WELL_FORMEDNESS_DST, &msg)
+    }
+}
+
+/// t is the threshold for encryption and apk is the aggregated public key.
+/// rng must be a cryptographic rng such as OsRng: it draws the encapsulated key
+pub fn encrypt<E: Pairing, R: RngCore>(
+    apk: &AggregateKey<E>,
+    t: usize,
+    params: &UniversalParams<E>,
+    rng: &mut R,
+) -> Ciphertext<E> {
+    encrypt_with_label(apk, t, params, &[], rng)
 }
 
-/// t is the threshold for encryption and apk is the aggregated public key
-pub fn encrypt<E: Pairing>(
+/// same as encrypt, but the ciphertext is bound to label (associated data)
+/// and can be checked with Ciphertext::verify before decryption
+pub fn encrypt_with_label<E: Pairing, R: RngCore>(
     apk: &AggregateKey<E>,
     t: usize,
     params: &UniversalParams<E>,
+    label: &[u8],
+    rng: &mut R,
 ) -> Ciphertext<E> {
-    let mut rng = ark_std::test_rng();
-    let gamma = E::ScalarField::rand(&mut rng);
+    encrypt_internal(apk, t, params, label, false, rng)
+}
+
+/// same as encrypt_with_label, but additionally attaches a WellFormednessProof
+/// that can be checked with Ciphertext::verify_well_formed
+pub fn encrypt_with_proof<E: Pairing, R: RngCore>(
+    apk: &AggregateKey<E>,
+    t: usize,
+    params: &UniversalParams<E>,
+    label: &[u8],
+    rng: &mut R,
+) -> Ciphertext<E> {
+    encrypt_internal(apk, t, params, label, true, rng)
+}
+
+#[tracing::instrument(name = "encrypt", skip_all, fields(t = t, n = apk.pk.len(), prove_well_formed = prove_well_formed))]
+fn encrypt_internal<E: Pairing, R: RngCore>(
+    apk: &AggregateKey<E>,
+    t: usize,
+    params: &UniversalParams<E>,
+    label: &[u8],
+    prove_well_formed: bool,
+    rng: &mut R,
+) -> Ciphertext<E> {
+    let gamma = E::ScalarField::rand(rng);
     let gamma_g2 = params.powers_of_h[0] * gamma;
 
     let g = params.powers_of_g[0];
@@ -57,7 +291,7 @@ pub fn encrypt<E: Pairing>(
     let mut s: [E::ScalarField; 5] = [E::ScalarField::zero(); 5];
 
     s.iter_mut()
-        .for_each(|mut s| *s = E::ScalarField::rand(&mut rng));
+        .for_each(|s| *s = E::ScalarField::rand(rng));
 
     // sa1[0] = s0*ask + s3*g^{tau^t} + s4*g
     sa1[0] = (apk.ask * s[0]) + (params.powers_of_g[t] * s[3]) + (params.powers_of_g[0] * s[4]);
@@ -86,13 +320,45 @@ pub fn encrypt<E: Pairing>(
     // enc_key = s4*e_gh
     let enc_key = apk.e_gh.mul(s[4]);
 
-    Ciphertext {
+    let mut ct = Ciphertext {
         gamma_g2,
         sa1,
         sa2,
         enc_key,
         t,
+        degree: params.degree(),
+        params_fingerprint: params.fingerprint(),
+        agg_key_fingerprint: apk.fingerprint(),
+        label_proof: LabelProof {
+            c: E::ScalarField::zero(),
+            z: E::ScalarField::zero(),
+        },
+        wf_proof: None,
+    };
+
+    if prove_well_formed {
+        let ek = EncryptionKey::new(apk, params);
+        let w = [s[0], s[1], s[2], s[3], s[4], gamma];
+
+        let mut r = [E::ScalarField::zero(); 6];
+        r.iter_mut().for_each(|r| *r = E::ScalarField::rand(rng));
+        let (a1, a2) = ek.encryption_map(t, &gamma_g2, &r);
+
+        let c = ct.wf_challenge(&ek, &a1, &a2);
+        let mut z = r;
+        for (z, w) in z.iter_mut().zip(w.iter()) {
+            *z += c * w;
+        }
+        ct.wf_proof = Some(WellFormednessProof { c, z });
     }
+
+    // prove knowledge of gamma, binding label and the rest of the ciphertext
+    let r = E::ScalarField::rand(rng);
+    let r_g2 = params.powers_of_h[0] * r;
+    let c = ct.label_challenge(label, apk, &r_g2);
+    ct.label_proof = LabelProof { c, z: r + c * gamma };
+
+    ct
 }
 
 #[cfg(test)]
@@ -103,6 +369,7 @@ mod tests {
         setup::{PublicKey, SecretKey},
     };
     use ark_poly::univariate::DensePolynomial;
+    use rand::rngs::OsRng;
 
     type E = ark_bls12_381::Bls12_381;
     type G1 = <E as Pairing>::G1;
@@ -120,11 +387,16 @@ mod tests {
 
         for i in 0..n {
             sk.push(SecretKey::<E>::new(&mut rng));
-            pk.push(sk[i].get_pk(0, &params, n))
+            pk.push(sk[i].get_pk(i, &params, n))
         }
 
-        let ak = AggregateKey::<E>::new(pk, &params);
-        let ct = encrypt::<E>(&ak, 2, &params);
+        let ak = AggregateKey::<E>::new(pk, &params).unwrap();
+        let ct = encrypt::<E, _>(&ak, 2, &params, &mut rng);
+
+        // every encryption draws a fresh key
+        let a = encrypt::<E, _>(&ak, 2, &params, &mut OsRng);
+        let b = encrypt::<E, _>(&ak, 2, &params, &mut OsRng);
+        assert_ne!(a.enc_key, b.enc_key);
 
         let mut ct_bytes = Vec::new();
         ct.serialize_compressed(&mut ct_bytes).unwrap();
@@ -145,4 +417,78 @@ mod tests {
         println!("G2 len: {} bytes", g2_bytes.len());
         println!("GT len: {} bytes", e_gh_bytes.len());
     }
+
+    #[test]
+    fn test_label_binding() {
+        let mut rng = ark_std::test_rng();
+        let n = 8;
+        let params = KZG10::<E, UniPoly381>::setup(n, &mut rng).unwrap();
+
+        let mut pk: Vec<PublicKey<E>> = Vec::new();
+        for i in 0..n {
+            let sk = SecretKey::<E>::new(&mut rng);
+            pk.push(sk.get_pk(i, &params, n))
+        }
+
+        let ak = AggregateKey::<E>::new(pk, &params).unwrap();
+        let ct = encrypt_with_label::<E, _>(&ak, 2, &params, b"label", &mut rng);
+        assert!(ct.verify(b"label", &ak, &params));
+        assert!(!ct.verify(b"other label", &ak, &params));
+
+        // mixing components from another ciphertext breaks the proof
+        let other = encrypt_with_label::<E, _>(&ak, 2, &params, b"label", &mut rng);
+        let mut mauled = ct.clone();
+        mauled.sa1 = other.sa1;
+        assert!(!mauled.verify(b"label", &ak, &params));
+
+        let mut mauled = ct.clone();
+        mauled.t = 3;
+        assert!(!mauled.verify(b"label", &ak, &params));
+    }
+
+    #[test]
+    fn test_well_formedness_proof() {
+        let mut rng = ark_std::test_rng();
+        let n = 8;
+        let params = KZG10::<E, UniPoly381>::setup(n, &mut rng).unwrap();
+
+        let mut pk: Vec<PublicKey<E>> = Vec::new();
+        for i in 0..n {
+            let sk = SecretKey::<E>::new(&mut rng);
+            pk.push(sk.get_pk(i, &params, n))
+        }
+
+        let ak = AggregateKey::<E>::new(pk, &params).unwrap();
+        let ek = EncryptionKey::new(&ak, &params);
+
+        let ct = encrypt_with_proof::<E, _>(&ak, 3, &params, b"", &mut rng);
+        assert!(ct.verify(b"", &ak, &params));
+        assert!(ct.verify_well_formed(&ek));
+
+        let mut bytes = Vec::new();
+        ct.serialize_compressed(&mut bytes).unwrap();
+        let ct = Ciphertext::<E>::deserialize_compressed(&bytes[..]).unwrap();
+        assert!(ct.verify_well_formed(&ek));
+
+        // a different threshold or a tampered component is rejected
+        let mut mauled = ct.clone();
+        mauled.t = 2;
+        assert!(!mauled.verify_well_formed(&ek));
+
+        let mut mauled = ct.clone();
+        mauled.sa2[5] = mauled.sa2[4];
+        assert!(!mauled.verify_well_formed(&ek));
+
+        // the proof is bound to the statement, not just to the group elements
+        let mut mauled = ct.clone();
+        mauled.degree += 1;
+        assert!(!mauled.verify_well_formed(&ek));
+
+        let mut other_ek = ek.clone();
+        other_ek.agg_key_fingerprint = [0; 32];
+        assert!(!ct.verify_well_formed(&other_ek));
+
+        let ct = encrypt_with_label::<E, _>(&ak, 3, &params, b"", &mut rng);
+        assert!(!ct.verify_well_formed(&ek));
+    }
 }
@@ -0,0 +1,491 @@
+//! C ABI over opaque handles, for services that are not written in Rust.
+//! The header is include/silent_threshold.h, generated by cbindgen (see tests/ffi.rs).
+//!
+//! Conventions:
+//! - every function returns a Status; results are written through out pointers
+//! - handles are created by st_*_from_bytes and friends and released with st_*_free
+//! - byte buffers are owned by the caller. Functions producing bytes take the buffer and a
+//!   pointer to its capacity, and set it to the number of bytes written. If the buffer is NULL
+//!   or too small they return ST_STATUS_BUFFER_TOO_SMALL with the required size instead,
+//!   so callers can ask for the size first
+//! - objects use the canonical uncompressed encoding, the same bytes as the wasm exports
+//! - slot 0 is the dummy party: st_aggregate_key_new derives its key and st_combine its
+//!   partial decryption, so callers only deal with parties 1..n
+//!
+//! Pointers must be valid for the lengths passed with them, handles must come from this
+//! library and be freed once. Handles may be shared between threads for reading.
+#![allow(clippy::missing_safety_doc)]
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+use ark_ec::pairing::{Pairing, PairingOutput};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::Zero;
+use rand::rngs::OsRng;
+
+use crate::decryption::DecryptionContext;
+use crate::encryption::{encrypt_with_label, Ciphertext as Ct};
+use crate::kzg::UniversalParams;
+use crate::setup::{AggregateKey as AggKey, PublicKey as Pk, SecretKey as Sk};
+use crate::utils::{checked, to_bytes};
+
+type E = ark_bls12_381::Bls12_381;
+type G2 = <E as Pairing>::G2;
+
+#[repr(C)]
+#[derive(Debug, PartialEq, Eq)]
+pub enum Status {
+    Ok = 0,
+    /// A required pointer argument was NULL.
+    NullPointer,
+    /// Input bytes are not a valid object of the expected type.
+    InvalidEncoding,
+    /// The output buffer is NULL or too small; the required size was written to its length.
+    BufferTooSmall,
+    /// An argument is out of range, e.g. a slot id or threshold.
+    InvalidArgument,
+    /// A proof, label or partial decryption did not verify.
+    VerificationFailed,
+    /// The objects were built for different params or committees.
+    Mismatch,
+    /// Fewer valid partial decryptions than the ciphertext's threshold.
+    ThresholdNotMet,
+    /// A bug in the library; the operation was abandoned.
+    Panic,
+}
+
+pub struct Params(UniversalParams<E>);
+pub struct SecretKey(Sk<E>);
+pub struct PublicKey(Pk<E>);
+// the aggregate key and the fingerprint of the params it was built for
+pub struct AggregateKey(AggKey<E>, [u8; 32]);
+pub struct Ciphertext(Ct<E>);
+
+/// runs f, turning errors and panics into a Status
+fn guard(f: impl FnOnce() -> Result<(), Status>) -> Status {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => Status::Ok,
+        Ok(Err(status)) => status,
+        Err(_) => Status::Panic,
+    }
+}
+
+unsafe fn input<'a>(bytes: *const u8, len: usize) -> Result<&'a [u8], Status> {
+    if len == 0 {
+        return Ok(&[]);
+    }
+    if bytes.is_null() {
+        return Err(Status::NullPointer);
+    }
+    Ok(std::slice::from_raw_parts(bytes, len))
+}
+
+unsafe fn handle<'a, T>(ptr: *const T) -> Result<&'a T, Status> {
+    ptr.as_ref().ok_or(Status::NullPointer)
+}
+
+unsafe fn put<T>(out: *mut *mut T, value: T) -> Result<(), Status> {
+    if out.is_null() {
+        return Err(Status::NullPointer);
+    }
+    *out = Box::into_raw(Box::new(value));
+    Ok(())
+}
+
+unsafe fn output(bytes: &[u8], out: *mut u8, out_len: *mut usize) -> Result<(), Status> {
+    let capacity = out_len.as_mut().ok_or(Status::NullPointer)?;
+    if out.is_null() || *capacity < bytes.len() {
+        *capacity = bytes.len();
+        return Err(Status::BufferTooSmall);
+    }
+    ptr::copy_nonoverlapping(bytes.as_ptr(), out, bytes.len());
+    *capacity = bytes.len();
+    Ok(())
+}
+
+unsafe fn free<T>(ptr: *mut T) {
+    if !ptr.is_null() {
+        drop(Box::from_raw(ptr));
+    }
+}
+
+unsafe fn parse<T: CanonicalDeserialize>(bytes: *const u8, len: usize) -> Result<T, Status> {
+    checked(input(bytes, len)?).map_err(|_| Status::InvalidEncoding)
+}
+
+// params
+
+#[no_mangle]
+pub unsafe extern "C" fn st_params_from_bytes(bytes: *const u8, len: usize, out: *mut *mut Params) -> Status {
+    guard(|| put(out, Params(parse(bytes, len)?)))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn st_params_to_bytes(params: *const Params, out: *mut u8, out_len: *mut usize) -> Status {
+    guard(|| output(&to_bytes(&handle(params)?.0), out, out_len))
+}
+
+/// the largest committee size the params support
+#[no_mangle]
+pub unsafe extern "C" fn st_params_degree(params: *const Params, degree: *mut usize) -> Status {
+    guard(|| {
+        *degree.as_mut().ok_or(Status::NullPointer)? = handle(params)?.0.degree();
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn st_params_free(params: *mut Params) {
+    free(params)
+}
+
+// secret keys
+
+/// a fresh secret key from the operating system's randomness
+#[no_mangle]
+pub unsafe extern "C" fn st_secret_key_generate(out: *mut *mut SecretKey) -> Status {
+    guard(|| put(out, SecretKey(Sk::new(&mut OsRng))))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn st_secret_key_from_bytes(bytes: *const u8, len: usize, out: *mut *mut SecretKey) -> Status {
+    guard(|| {
+        let sk = Sk::from_secret_bytes(input(bytes, len)?).map_err(|_| Status::InvalidEncoding)?;
+        put(out, SecretKey(sk))
+    })
+}
+
+/// writes the 32 byte secret scalar, for the caller to store safely
+#[no_mangle]
+pub unsafe extern "C" fn st_secret_key_export_secret_bytes(
+    sk: *const SecretKey,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> Status {
+    guard(|| output(&handle(sk)?.0.export_secret_bytes(), out, out_len))
+}
+
+/// the public key and hints of sk for slot id in a committee of n slots, a power of two
+/// equal to the degree of params
+#[no_mangle]
+pub unsafe extern "C" fn st_secret_key_public_key(
+    sk: *const SecretKey,
+    params: *const Params,
+    id: usize,
+    n: usize,
+    out: *mut *mut PublicKey,
+) -> Status {
+    guard(|| {
+        let params = &handle(params)?.0;
+        if !n.is_power_of_two() || n < 2 || n != params.degree() || id == 0 || id >= n {
+            return Err(Status::InvalidArgument);
+        }
+        put(out, PublicKey(handle(sk)?.0.get_pk(id, params, n)))
+    })
+}
+
+/// writes the partial decryption of ct, the canonical encoding of a G2 element.
+/// ct must verify against label and agg_key, as in st_ciphertext_verify
+#[no_mangle]
+pub unsafe extern "C" fn st_partial_decryption(
+    sk: *const SecretKey,
+    ct: *const Ciphertext,
+    label: *const u8,
+    label_len: usize,
+    agg_key: *const AggregateKey,
+    params: *const Params,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> Status {
+    guard(|| {
+        let (ct, agg_key, params) = (&handle(ct)?.0, &handle(agg_key)?.0, &handle(params)?.0);
+        let partial = handle(sk)?
+            .0
+            .partial_decryption(ct, input(label, label_len)?, agg_key, params)
+            .map_err(|_| Status::VerificationFailed)?;
+        output(&to_bytes(&partial), out, out_len)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn st_secret_key_free(sk: *mut SecretKey) {
+    free(sk)
+}
+
+// public keys
+
+#[no_mangle]
+pub unsafe extern "C" fn st_public_key_from_bytes(bytes: *const u8, len: usize, out: *mut *mut PublicKey) -> Status {
+    guard(|| put(out, PublicKey(parse(bytes, len)?)))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn st_public_key_to_bytes(pk: *const PublicKey, out: *mut u8, out_len: *mut usize) -> Status {
+    guard(|| output(&to_bytes(&handle(pk)?.0), out, out_len))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn st_public_key_id(pk: *const PublicKey, id: *mut usize) -> Status {
+    guard(|| {
+        *id.as_mut().ok_or(Status::NullPointer)? = handle(pk)?.0.id;
+        Ok(())
+    })
+}
+
+/// checks a partial decryption of ct against the public key of the party that sent it
+#[no_mangle]
+pub unsafe extern "C" fn st_verify_partial_decryption(
+    pk: *const PublicKey,
+    ct: *const Ciphertext,
+    partial: *const u8,
+    len: usize,
+) -> Status {
+    guard(|| {
+        let partial: G2 = parse(partial, len)?;
+        if !handle(pk)?.0.verify_partial_decryption(&handle(ct)?.0, &partial) {
+            return Err(Status::VerificationFailed);
+        }
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn st_public_key_free(pk: *mut PublicKey) {
+    free(pk)
+}
+
+// aggregate keys
+
+/// aggregates the public keys of slots 1..len, in any order, together with the dummy party
+#[no_mangle]
+pub unsafe extern "C" fn st_aggregate_key_new(
+    pks: *const *const PublicKey,
+    len: usize,
+    params: *const Params,
+    out: *mut *mut AggregateKey,
+) -> Status {
+    guard(|| {
+        let params = &handle(params)?.0;
+        if len == 0 || pks.is_null() {
+            return Err(Status::InvalidArgument);
+        }
+        let mut pk = std::slice::from_raw_parts(pks, len)
+            .iter()
+            .map(|&pk| handle(pk).map(|pk| pk.0.clone()))
+            .collect::<Result<Vec<_>, _>>()?;
+        pk.sort_by_key(|pk| pk.id);
+
+        let n = len + 1;
+        if pk.iter().zip(1..).any(|(pk, slot)| pk.id != slot || pk.sk_li_by_z.len() != n) {
+            return Err(Status::InvalidArgument);
+        }
+
+        let mut dummy = Sk::new(&mut OsRng);
+        dummy.nullify();
+        pk.insert(0, dummy.get_pk(0, params, n));
+
+        let agg_key = AggKey::new(pk, params).map_err(aggregate_status)?;
+        put(out, AggregateKey(agg_key, params.fingerprint()))
+    })
+}
+
+/// reads the public keys written by st_aggregate_key_to_bytes and aggregates them again
+/// over params, with the same checks as st_aggregate_key_new
+#[no_mangle]
+pub unsafe extern "C" fn st_aggregate_key_from_bytes(
+    bytes: *const u8,
+    len: usize,
+    params: *const Params,
+    out: *mut *mut AggregateKey,
+) -> Status {
+    guard(|| {
+        let params = &handle(params)?.0;
+        let agg_key = AggKey::from_bytes(input(bytes, len)?, params).map_err(aggregate_status)?;
+        put(out, AggregateKey(agg_key, params.fingerprint()))
+    })
+}
+
+fn aggregate_status(err: crate::setup::Error) -> Status {
+    match err {
+        crate::setup::Error::InvalidEncoding(_) => Status::InvalidEncoding,
+        crate::setup::Error::InvalidProofOfPossession { .. } => Status::VerificationFailed,
+        _ => Status::Mismatch,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn st_aggregate_key_to_bytes(
+    agg_key: *const AggregateKey,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> Status {
+    guard(|| output(&to_bytes(&handle(agg_key)?.0), out, out_len))
+}
+
+/// the number of slots, including the dummy party
+#[no_mangle]
+pub unsafe extern "C" fn st_aggregate_key_size(agg_key: *const AggregateKey, size: *mut usize) -> Status {
+    guard(|| {
+        *size.as_mut().ok_or(Status::NullPointer)? = handle(agg_key)?.0.pk.len();
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn st_aggregate_key_free(agg_key: *mut AggregateKey) {
+    free(agg_key)
+}
+
+// ciphertexts
+
+/// encrypts to the committee with threshold t and label as associated data. the
+/// encapsulated key is written to key, and is not part of the ciphertext's encoding.
+/// ST_STATUS_MISMATCH is returned if agg_key was built for other params
+#[no_mangle]
+pub unsafe extern "C" fn st_encrypt(
+    agg_key: *const AggregateKey,
+    params: *const Params,
+    t: usize,
+    label: *const u8,
+    label_len: usize,
+    out: *mut *mut Ciphertext,
+    key: *mut u8,
+    key_len: *mut usize,
+) -> Status {
+    guard(|| {
+        let (AggregateKey(agg_key, built_for), params) = (handle(agg_key)?, &handle(params)?.0);
+        if *built_for != params.fingerprint() {
+            return Err(Status::Mismatch);
+        }
+        if t == 0 || t >= agg_key.pk.len() {
+            return Err(Status::InvalidArgument);
+        }
+        let label = input(label, label_len)?;
+        if out.is_null() {
+            return Err(Status::NullPointer);
+        }
+        // size the key buffer before doing any work
+        let required = PairingOutput::<E>::zero().uncompressed_size();
+        let capacity = key_len.as_mut().ok_or(Status::NullPointer)?;
+        if key.is_null() || *capacity < required {
+            *capacity = required;
+            return Err(Status::BufferTooSmall);
+        }
+
+        let mut ct = encrypt_with_label(agg_key, t, params, label, &mut OsRng);
+        let enc_key = std::mem::replace(&mut ct.enc_key, PairingOutput::zero());
+        output(&to_bytes(&enc_key), key, key_len)?;
+        put(out, Ciphertext(ct))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn st_ciphertext_from_bytes(bytes: *const u8, len: usize, out: *mut *mut Ciphertext) -> Status {
+    guard(|| put(out, Ciphertext(parse(bytes, len)?)))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn st_ciphertext_to_bytes(ct: *const Ciphertext, out: *mut u8, out_len: *mut usize) -> Status {
+    guard(|| output(&to_bytes(&handle(ct)?.0), out, out_len))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn st_ciphertext_threshold(ct: *const Ciphertext, t: *mut usize) -> Status {
+    guard(|| {
+        *t.as_mut().ok_or(Status::NullPointer)? = handle(ct)?.0.t;
+        Ok(())
+    })
+}
+
+/// checks that ct was encrypted to agg_key under params with this label.
+/// parties should run this before releasing a partial decryption
+#[no_mangle]
+pub unsafe extern "C" fn st_ciphertext_verify(
+    ct: *const Ciphertext,
+    label: *const u8,
+    label_len: usize,
+    agg_key: *const AggregateKey,
+    params: *const Params,
+) -> Status {
+    guard(|| {
+        let (ct, agg_key, params) = (&handle(ct)?.0, &handle(agg_key)?.0, &handle(params)?.0);
+        DecryptionContext::new(agg_key, params)
+            .prepare(ct)
+            .map_err(|_| Status::Mismatch)?;
+        if !ct.verify(input(label, label_len)?, agg_key, params) {
+            return Err(Status::VerificationFailed);
+        }
+        Ok(())
+    })
+}
+
+/// recovers the encapsulated key of ct. partials[i] is the partial decryption of slot i
+/// (partial_lens[i] bytes), or NULL if that party did not answer; slot 0 is ignored, and
+/// ST_STATUS_MISMATCH is returned if slot 0 of agg_key is not the dummy party.
+/// every given partial is parsed and must verify, and the first t are used
+#[no_mangle]
+pub unsafe extern "C" fn st_combine(
+    agg_key: *const AggregateKey,
+    params: *const Params,
+    ct: *const Ciphertext,
+    partials: *const *const u8,
+    partial_lens: *const usize,
+    len: usize,
+    key: *mut u8,
+    key_len: *mut usize,
+) -> Status {
+    guard(|| {
+        let (agg_key, params, ct) = (&handle(agg_key)?.0, &handle(params)?.0, &handle(ct)?.0);
+        let n = agg_key.pk.len();
+        if len > n {
+            return Err(Status::InvalidArgument);
+        }
+        if len > 0 && (partials.is_null() || partial_lens.is_null()) {
+            return Err(Status::NullPointer);
+        }
+
+        let ctx = DecryptionContext::new(agg_key, params);
+        let prepared = ctx.prepare(ct).map_err(|_| Status::Mismatch)?;
+
+        // the dummy party's secret key is 1
+        let mut values = vec![G2::zero(); n];
+        let mut selector = vec![false; n];
+        values[0] = ct.gamma_g2;
+        selector[0] = true;
+        if !agg_key.pk[0].verify_partial_decryption(ct, &values[0]) {
+            return Err(Status::Mismatch);
+        }
+
+        let mut selected = 0;
+        for i in 1..len {
+            let partial = *partials.add(i);
+            if partial.is_null() {
+                continue;
+            }
+            let value: G2 = parse(partial, *partial_lens.add(i))?;
+            if !agg_key.pk[i].verify_partial_decryption(ct, &value) {
+                return Err(Status::VerificationFailed);
+            }
+            if selected == ct.t {
+                continue;
+            }
+            values[i] = value;
+            selector[i] = true;
+            selected += 1;
+        }
+        if selected < ct.t {
+            return Err(Status::ThresholdNotMet);
+        }
+
+        let enc_key = ctx
+            .agg_dec(&values, &prepared, &selector)
+            .map_err(|_| Status::Mismatch)?;
+        output(&to_bytes(&enc_key), key, key_len)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn st_ciphertext_free(ct: *mut Ciphertext) {
+    free(ct)
+}
@@ -0,0 +1,332 @@
+//! Loading powers of tau from public BLS12-381 ceremonies into UniversalParams,
+//! so that no party has to trust KZG10::setup.
+//!
+//! Supported layouts:
+//! - the Ethereum KZG ceremony `trusted_setup.json` (`g1_monomial`, `g2_monomial`;
+//!   `g1_lagrange` is not needed since hints are computed from the monomial basis).
+//!   It only has 65 G2 powers, so it supports a degree, and thus a committee, of at most 64
+//! - snarkjs `.ptau` files as used by the Hermez and Filecoin ceremonies
+//!
+//! The imported powers are truncated to `max_degree` and checked with UniversalParams::check.
+
+use std::fs::File;
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use ark_bls12_381::{Bls12_381, Fq, Fq2, G1Affine, G2Affine};
+use ark_ec::AffineRepr;
+use ark_ff::{BigInt, PrimeField, Zero};
+use ark_serialize::{CanonicalDeserialize, Valid};
+use serde::Deserialize;
+
+use crate::kzg::UniversalParams;
+
+type E = Bls12_381;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Json(serde_json::Error),
+
+    /// The file does not follow the expected layout.
+    Malformed(String),
+
+    /// The ceremony does not have enough powers for the requested degree.
+    NotEnoughPowers {
+        /// The number of powers available in the ceremony output.
+        available: usize,
+        /// The number of powers needed for the requested degree.
+        needed: usize,
+    },
+
+    /// The loaded points are not powers of a single tau.
+    InconsistentParams,
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Error::Json(err)
+    }
+}
+
+#[derive(Deserialize)]
+struct EthereumSetup {
+    g1_monomial: Vec<String>,
+    g2_monomial: Vec<String>,
+}
+
+/// reads the Ethereum KZG ceremony JSON and keeps the powers up to max_degree.
+/// the ceremony has 65 G2 powers, so max_degree can be at most 64
+pub fn from_ethereum_json<R: Read>(reader: R, max_degree: usize) -> Result<UniversalParams<E>, Error> {
+    let setup: EthereumSetup = serde_json::from_reader(reader)?;
+    let needed = max_degree + 1;
+    check_available(setup.g1_monomial.len().min(setup.g2_monomial.len()), needed)?;
+
+    // points are hex encoded in the zcash compressed format, which is what arkworks uses for BLS12-381
+    let powers_of_g = setup.g1_monomial[..needed]
+        .iter()
+        .map(|s| G1Affine::deserialize_compressed(&decode_hex(s)?[..]).map_err(malformed))
+        .collect::<Result<Vec<_>, _>>()?;
+    let powers_of_h = setup.g2_monomial[..needed]
+        .iter()
+        .map(|s| G2Affine::deserialize_compressed(&decode_hex(s)?[..]).map_err(malformed))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    finish(powers_of_g, powers_of_h)
+}
+
+pub fn from_ethereum_json_file<P: AsRef<Path>>(path: P, max_degree: usize) -> Result<UniversalParams<E>, Error> {
+    from_ethereum_json(BufReader::new(File::open(path)?), max_degree)
+}
+
+/// reads a snarkjs .ptau file over BLS12-381 and keeps the powers up to max_degree
+pub fn from_ptau<R: Read + Seek>(mut reader: R, max_degree: usize) -> Result<UniversalParams<E>, Error> {
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+    if &magic != b"ptau" {
+        return Err(Error::Malformed("missing ptau magic".into()));
+    }
+    let _version = read_u32(&mut reader)?;
+    let num_sections = read_u32(&mut reader)?;
+
+    // section id -> (offset, size)
+    let mut sections = vec![None; 16];
+    for _ in 0..num_sections {
+        let id = read_u32(&mut reader)? as usize;
+        let size = read_u64(&mut reader)?;
+        let offset = reader.stream_position()?;
+        if id < sections.len() {
+            sections[id] = Some((offset, size));
+        }
+        reader.seek(SeekFrom::Current(size as i64))?;
+    }
+    let section = |id: usize| {
+        sections[id].ok_or_else(|| Error::Malformed(format!("missing section {}", id)))
+    };
+
+    // header: n8, q, power, ceremony power
+    reader.seek(SeekFrom::Start(section(1)?.0))?;
+    let n8 = read_u32(&mut reader)? as usize;
+    if n8 != 48 {
+        return Err(Error::Malformed("not a BLS12-381 ceremony".into()));
+    }
+    let q = read_bigint(&mut reader)?;
+    if q != Fq::MODULUS {
+        return Err(Error::Malformed("not a BLS12-381 ceremony".into()));
+    }
+    let power = read_u32(&mut reader)?;
+    let available = 1usize
+        .checked_shl(power)
+        .ok_or_else(|| Error::Malformed(format!("power {} out of range", power)))?;
+
+    let needed = max_degree + 1;
+    check_available(available, needed)?;
+
+    reader.seek(SeekFrom::Start(section(2)?.0))?;
+    let powers_of_g = (0..needed)
+        .map(|_| read_g1(&mut reader))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    reader.seek(SeekFrom::Start(section(3)?.0))?;
+    let powers_of_h = (0..needed)
+        .map(|_| read_g2(&mut reader))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    finish(powers_of_g, powers_of_h)
+}
+
+pub fn from_ptau_file<P: AsRef<Path>>(path: P, max_degree: usize) -> Result<UniversalParams<E>, Error> {
+    from_ptau(BufReader::new(File::open(path)?), max_degree)
+}
+
+fn finish(powers_of_g: Vec<G1Affine>, powers_of_h: Vec<G2Affine>) -> Result<UniversalParams<E>, Error> {
+    let params = UniversalParams::new(powers_of_g, powers_of_h);
+    params.check().map_err(|_| Error::InconsistentParams)?;
+    Ok(params)
+}
+
+fn check_available(available: usize, needed: usize) -> Result<(), Error> {
+    if available < needed {
+        Err(Error::NotEnoughPowers { available, needed })
+    } else {
+        Ok(())
+    }
+}
+
+fn malformed<T: ToString>(err: T) -> Error {
+    Error::Malformed(err.to_string())
+}
+
+// usize::is_multiple_of needs Rust 1.87, newer than anything else here requires
+#[allow(clippy::manual_is_multiple_of)]
+fn decode_hex(s: &str) -> Result<Vec<u8>, Error> {
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    if s.len() % 2 != 0 {
+        return Err(Error::Malformed("odd length hex string".into()));
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).map_err(malformed))
+        .collect()
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> Result<u32, Error> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> Result<u64, Error> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+fn read_bigint<R: Read>(reader: &mut R) -> Result<BigInt<6>, Error> {
+    let mut limbs = [0u64; 6];
+    for limb in limbs.iter_mut() {
+        *limb = read_u64(reader)?;
+    }
+    Ok(BigInt::new(limbs))
+}
+
+/// ptau stores base field elements little endian in Montgomery form with R = 2^384,
+/// which is also the internal representation of Fq
+fn read_fq<R: Read>(reader: &mut R) -> Result<Fq, Error> {
+    let repr = read_bigint(reader)?;
+    if repr >= Fq::MODULUS {
+        return Err(Error::Malformed("field element out of range".into()));
+    }
+    Ok(Fq::new_unchecked(repr))
+}
+
+fn read_g1<R: Read>(reader: &mut R) -> Result<G1Affine, Error> {
+    let x = read_fq(reader)?;
+    let y = read_fq(reader)?;
+    if x.is_zero() && y.is_zero() {
+        return Ok(G1Affine::zero());
+    }
+    let p = G1Affine::new_unchecked(x, y);
+    if !p.is_on_curve() || !p.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(Error::Malformed("G1 point not in the subgroup".into()));
+    }
+    Ok(p)
+}
+
+fn read_g2<R: Read>(reader: &mut R) -> Result<G2Affine, Error> {
+    let x = Fq2::new(read_fq(reader)?, read_fq(reader)?);
+    let y = Fq2::new(read_fq(reader)?, read_fq(reader)?);
+    if x.is_zero() && y.is_zero() {
+        return Ok(G2Affine::zero());
+    }
+    let p = G2Affine::new_unchecked(x, y);
+    if !p.is_on_curve() || !p.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(Error::Malformed("G2 point not in the subgroup".into()));
+    }
+    Ok(p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kzg::KZG10;
+    use ark_ec::pairing::Pairing;
+    use ark_poly::univariate::DensePolynomial;
+    use ark_serialize::CanonicalSerialize;
+    use std::io::Cursor;
+
+    type UniPoly381 = DensePolynomial<<E as Pairing>::ScalarField>;
+
+    fn to_hex<T: CanonicalSerialize>(p: &T) -> String {
+        let mut bytes = Vec::new();
+        p.serialize_compressed(&mut bytes).unwrap();
+        let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+        format!("0x{}", hex)
+    }
+
+    fn write_fq(out: &mut Vec<u8>, f: &Fq) {
+        for limb in f.0 .0.iter() {
+            out.extend_from_slice(&limb.to_le_bytes());
+        }
+    }
+
+    fn write_section(out: &mut Vec<u8>, id: u32, data: &[u8]) {
+        out.extend_from_slice(&id.to_le_bytes());
+        out.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        out.extend_from_slice(data);
+    }
+
+    #[test]
+    fn test_import() {
+        let mut rng = ark_std::test_rng();
+        // the ptau layout has 2^power G2 powers and twice as many G1 powers, less one
+        let g2_powers: usize = 8;
+        let power = g2_powers.trailing_zeros();
+        let params = KZG10::<E, UniPoly381>::setup(2 * g2_powers - 2, &mut rng).unwrap();
+
+        // Ethereum ceremony JSON
+        let json = format!(
+            "{{\"g1_monomial\": [{}], \"g1_lagrange\": [], \"g2_monomial\": [{}]}}",
+            params.powers_of_g.iter().map(|p| format!("\"{}\"", to_hex(p))).collect::<Vec<_>>().join(","),
+            params.powers_of_h[..=g2_powers].iter().map(|p| format!("\"{}\"", to_hex(p))).collect::<Vec<_>>().join(","),
+        );
+        let imported = from_ethereum_json(json.as_bytes(), 4).unwrap();
+        assert_eq!(imported.powers_of_g, params.powers_of_g[..5]);
+        assert_eq!(imported.powers_of_h, params.powers_of_h[..5]);
+        assert!(matches!(
+            from_ethereum_json(json.as_bytes(), 20),
+            Err(Error::NotEnoughPowers { .. })
+        ));
+
+        // snarkjs ptau, with an unrelated section in between
+        let mut header = Vec::new();
+        header.extend_from_slice(&48u32.to_le_bytes());
+        for limb in Fq::MODULUS.0.iter() {
+            header.extend_from_slice(&limb.to_le_bytes());
+        }
+        header.extend_from_slice(&power.to_le_bytes());
+        header.extend_from_slice(&power.to_le_bytes());
+
+        let mut tau_g1 = Vec::new();
+        for p in params.powers_of_g.iter() {
+            write_fq(&mut tau_g1, &p.x);
+            write_fq(&mut tau_g1, &p.y);
+        }
+        let mut tau_g2 = Vec::new();
+        for p in params.powers_of_h[..g2_powers].iter() {
+            write_fq(&mut tau_g2, &p.x.c0);
+            write_fq(&mut tau_g2, &p.x.c1);
+            write_fq(&mut tau_g2, &p.y.c0);
+            write_fq(&mut tau_g2, &p.y.c1);
+        }
+
+        let mut ptau = b"ptau".to_vec();
+        ptau.extend_from_slice(&1u32.to_le_bytes());
+        ptau.extend_from_slice(&4u32.to_le_bytes());
+        write_section(&mut ptau, 1, &header);
+        write_section(&mut ptau, 2, &tau_g1);
+        write_section(&mut ptau, 4, &[0u8; 7]);
+        write_section(&mut ptau, 3, &tau_g2);
+
+        let imported = from_ptau(Cursor::new(&ptau), 7).unwrap();
+        assert_eq!(imported.powers_of_g, params.powers_of_g[..8]);
+        assert_eq!(imported.powers_of_h, params.powers_of_h[..8]);
+
+        // a power too large for a shift is rejected, not wrapped
+        let mut crafted = ptau.clone();
+        let power_offset = 12 + 12 + 4 + 48;
+        crafted[power_offset..power_offset + 4].copy_from_slice(&64u32.to_le_bytes());
+        assert!(matches!(from_ptau(Cursor::new(&crafted), 7), Err(Error::Malformed(_))));
+
+        // a corrupted power is caught by the consistency check
+        let offset = ptau.len() - tau_g2.len() - 12 - 7 - 12 - tau_g1.len() + 3 * 96;
+        ptau[offset..offset + 96].copy_from_slice(&tau_g1[4 * 96..5 * 96]);
+        assert!(matches!(from_ptau(Cursor::new(&ptau), 7), Err(Error::InconsistentParams)));
+    }
+}
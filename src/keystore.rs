@@ -0,0 +1,314 @@
+//! Password-protected export of secret keys.
+//!
+//! A keystore is a JSON document holding the secret scalar encrypted with ChaCha20-Poly1305
+//! under a key derived from the passphrase with scrypt, together with the party's slot id,
+//! the fingerprint of its committee (when known) and the curve. Everything but the ciphertext
+//! is authenticated as associated data, so the metadata cannot be swapped between keystores,
+//! and fields this library does not know are rejected rather than left unauthenticated.
+
+use ark_bls12_381::Bls12_381;
+use ark_ec::pairing::Pairing;
+use ark_std::rand::{CryptoRng, RngCore};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::setup::SecretKey;
+
+const VERSION: u32 = 1;
+const KDF: &str = "scrypt";
+const CIPHER: &str = "chacha20poly1305";
+
+/// scrypt cost used for new keystores: N = 2^17, r = 8, p = 1 (128 MiB, as recommended by OWASP)
+pub const DEFAULT_LOG_N: u8 = 17;
+const DEFAULT_R: u32 = 8;
+const DEFAULT_P: u32 = 1;
+
+/// the most scrypt work import_encrypted agrees to do, as N * r * p
+const MAX_WORK: u64 = (1 << 20) * 8;
+
+/// Pairings with a stable name recorded in keystores.
+pub trait CurveName: Pairing {
+    const CURVE: &'static str;
+}
+
+impl CurveName for Bls12_381 {
+    const CURVE: &'static str = "BLS12-381";
+}
+
+/// What a keystore records about the key besides the key itself.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyMetadata {
+    /// The party's slot id in the committee.
+    pub slot: usize,
+    /// AggregateKey::fingerprint of the committee, if it was formed when the key was exported.
+    pub committee: Option<[u8; 32]>,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Json(serde_json::Error),
+
+    /// A field does not have the expected format.
+    Malformed(String),
+
+    /// The keystore uses a version, KDF or cipher this library does not know.
+    Unsupported(String),
+
+    /// The keystore holds a key for another curve.
+    CurveMismatch {
+        /// The curve recorded in the keystore.
+        found: String,
+        /// The curve of the key type being imported.
+        expected: &'static str,
+    },
+
+    /// The KDF parameters ask for more work than import_encrypted allows.
+    KdfTooExpensive,
+
+    /// The passphrase is wrong or the keystore was modified.
+    Decryption,
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Error::Json(err)
+    }
+}
+
+#[derive(Serialize)]
+struct Keystore {
+    #[serde(flatten)]
+    header: Header,
+    ciphertext: String,
+}
+
+/// the authenticated part of a keystore. the associated data is this header serialized
+/// again, so every field it is read from must be one it writes
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Header {
+    version: u32,
+    curve: String,
+    slot: usize,
+    committee: Option<String>,
+    kdf: Kdf,
+    cipher: Cipher,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Kdf {
+    function: String,
+    log_n: u8,
+    r: u32,
+    p: u32,
+    salt: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Cipher {
+    function: String,
+    nonce: String,
+}
+
+impl<E: CurveName> SecretKey<E> {
+    /// encrypts the key under passphrase into a JSON keystore recording metadata
+    pub fn export_encrypted<R: RngCore + CryptoRng>(
+        &self,
+        passphrase: &str,
+        metadata: &KeyMetadata,
+        rng: &mut R,
+    ) -> String {
+        let mut salt = [0u8; 32];
+        let mut nonce = [0u8; 12];
+        rng.fill_bytes(&mut salt);
+        rng.fill_bytes(&mut nonce);
+
+        let header = Header {
+            version: VERSION,
+            curve: E::CURVE.to_string(),
+            slot: metadata.slot,
+            committee: metadata.committee.map(hex::encode),
+            kdf: Kdf {
+                function: KDF.to_string(),
+                log_n: DEFAULT_LOG_N,
+                r: DEFAULT_R,
+                p: DEFAULT_P,
+                salt: hex::encode(salt),
+            },
+            cipher: Cipher {
+                function: CIPHER.to_string(),
+                nonce: hex::encode(nonce),
+            },
+        };
+
+        let cipher = cipher(passphrase, &header.kdf, &salt).expect("the default scrypt parameters are valid");
+        let aad = serde_json::to_vec(&header).unwrap();
+        let secret = self.export_secret_bytes();
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), Payload { msg: &secret, aad: &aad })
+            .expect("encryption of an in-memory buffer does not fail");
+
+        let keystore = Keystore {
+            header,
+            ciphertext: hex::encode(ciphertext),
+        };
+        serde_json::to_string_pretty(&keystore).unwrap()
+    }
+
+    /// decrypts a keystore written by export_encrypted
+    pub fn import_encrypted(keystore: &str, passphrase: &str) -> Result<(Self, KeyMetadata), Error> {
+        let (header, ciphertext) = parse(keystore)?;
+        let metadata = check_header::<E>(&header)?;
+
+        let salt = decode("salt", &header.kdf.salt)?;
+        let nonce: [u8; 12] = decode_array("nonce", &header.cipher.nonce)?;
+        let ciphertext = decode("ciphertext", &ciphertext)?;
+
+        let cipher = cipher(passphrase, &header.kdf, &salt)?;
+        let aad = serde_json::to_vec(&header).unwrap();
+        let secret = Zeroizing::new(
+            cipher
+                .decrypt(Nonce::from_slice(&nonce), Payload { msg: &ciphertext, aad: &aad })
+                .map_err(|_| Error::Decryption)?,
+        );
+        let sk = SecretKey::from_secret_bytes(&secret).map_err(|_| Error::Malformed("secret key".into()))?;
+        Ok((sk, metadata))
+    }
+}
+
+/// the metadata of a keystore, readable without the passphrase. it is NOT authenticated:
+/// anyone can edit it, and only import_encrypted notices. use it to pick a keystore, and
+/// the metadata import_encrypted returns for anything that matters
+pub fn read_metadata<E: CurveName>(keystore: &str) -> Result<KeyMetadata, Error> {
+    check_header::<E>(&parse(keystore)?.0)
+}
+
+/// splits a keystore into its header and the hex ciphertext. serde cannot deny unknown
+/// fields next to a flattened struct, so the ciphertext is taken out by hand first
+fn parse(keystore: &str) -> Result<(Header, String), Error> {
+    let mut fields: serde_json::Map<String, serde_json::Value> = serde_json::from_str(keystore)?;
+    let ciphertext = match fields.remove("ciphertext") {
+        Some(serde_json::Value::String(ciphertext)) => ciphertext,
+        _ => return Err(Error::Malformed("ciphertext".into())),
+    };
+    let header = serde_json::from_value(serde_json::Value::Object(fields))?;
+    Ok((header, ciphertext))
+}
+
+fn check_header<E: CurveName>(header: &Header) -> Result<KeyMetadata, Error> {
+    if header.version != VERSION {
+        return Err(Error::Unsupported(format!("version {}", header.version)));
+    }
+    if header.kdf.function != KDF {
+        return Err(Error::Unsupported(header.kdf.function.clone()));
+    }
+    if header.cipher.function != CIPHER {
+        return Err(Error::Unsupported(header.cipher.function.clone()));
+    }
+    if header.curve != E::CURVE {
+        return Err(Error::CurveMismatch {
+            found: header.curve.clone(),
+            expected: E::CURVE,
+        });
+    }
+
+    let committee = match &header.committee {
+        Some(committee) => Some(decode_array("committee", committee)?),
+        None => None,
+    };
+    Ok(KeyMetadata {
+        slot: header.slot,
+        committee,
+    })
+}
+
+fn cipher(passphrase: &str, kdf: &Kdf, salt: &[u8]) -> Result<ChaCha20Poly1305, Error> {
+    let work = 1u64.checked_shl(kdf.log_n.into()).unwrap_or(u64::MAX);
+    if work.saturating_mul(kdf.r.into()).saturating_mul(kdf.p.into()) > MAX_WORK {
+        return Err(Error::KdfTooExpensive);
+    }
+    let params = scrypt::Params::new(kdf.log_n, kdf.r, kdf.p, 32)
+        .map_err(|_| Error::Malformed("scrypt parameters".into()))?;
+
+    let mut key = Zeroizing::new([0u8; 32]);
+    scrypt::scrypt(passphrase.as_bytes(), salt, &params, &mut *key).expect("32 bytes is a valid scrypt output length");
+    Ok(ChaCha20Poly1305::new(Key::from_slice(&*key)))
+}
+
+fn decode(field: &str, value: &str) -> Result<Vec<u8>, Error> {
+    hex::decode(value).map_err(|_| Error::Malformed(field.to_string()))
+}
+
+fn decode_array<const N: usize>(field: &str, value: &str) -> Result<[u8; N], Error> {
+    decode(field, value)?
+        .try_into()
+        .map_err(|_| Error::Malformed(field.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+
+    type E = Bls12_381;
+
+    #[test]
+    fn test_keystore() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let sk = SecretKey::<E>::new(&mut rng);
+        let metadata = KeyMetadata {
+            slot: 3,
+            committee: Some([7; 32]),
+        };
+
+        let keystore = sk.export_encrypted("correct horse", &metadata, &mut rng);
+        assert!(!keystore.contains(&hex::encode(sk.export_secret_bytes())));
+        assert_eq!(read_metadata::<E>(&keystore).unwrap(), metadata);
+
+        let (imported, read) = SecretKey::<E>::import_encrypted(&keystore, "correct horse").unwrap();
+        assert_eq!(imported.bls_pk(), sk.bls_pk());
+        assert_eq!(read, metadata);
+
+        assert!(matches!(
+            SecretKey::<E>::import_encrypted(&keystore, "battery staple"),
+            Err(Error::Decryption)
+        ));
+
+        // the metadata is authenticated
+        let moved = keystore.replace("\"slot\": 3", "\"slot\": 2");
+        assert!(matches!(
+            SecretKey::<E>::import_encrypted(&moved, "correct horse"),
+            Err(Error::Decryption)
+        ));
+
+        // fields the header does not know are rejected, not left out of the associated data
+        let extended = keystore.replace("\"slot\": 3", "\"slot\": 3,\n  \"role\": \"admin\"");
+        assert!(matches!(read_metadata::<E>(&extended), Err(Error::Json(_))));
+        assert!(matches!(
+            SecretKey::<E>::import_encrypted(&extended, "correct horse"),
+            Err(Error::Json(_))
+        ));
+        let nested = keystore.replace("\"log_n\": 17", "\"log_n\": 17,\n    \"dklen\": 64");
+        assert!(matches!(
+            SecretKey::<E>::import_encrypted(&nested, "correct horse"),
+            Err(Error::Json(_))
+        ));
+
+        // and the KDF cost is bounded
+        let expensive = keystore.replace("\"log_n\": 17", "\"log_n\": 30");
+        assert!(matches!(
+            SecretKey::<E>::import_encrypted(&expensive, "correct horse"),
+            Err(Error::KdfTooExpensive)
+        ));
+
+        let other = keystore.replace("BLS12-381", "BN254");
+        assert!(matches!(
+            SecretKey::<E>::import_encrypted(&other, "correct horse"),
+            Err(Error::CurveMismatch { .. })
+        ));
+    }
+}
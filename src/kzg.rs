@@ -1,26 +1,171 @@
 //adapted from https://github.com/arkworks-rs/poly-commit/blob/master/src/kzg10/mod.rs
-#![allow(dead_code)]
-#![allow(unused_imports)]
 
-use ark_ec::{pairing::Pairing, CurveGroup, Group};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, Group};
 use ark_ec::{scalar_mul::fixed_base::FixedBase, VariableBaseMSM};
 use ark_ff::{One, PrimeField, UniformRand, Zero};
-use ark_poly::DenseUVPolynomial;
+use ark_poly::{DenseUVPolynomial, EvaluationDomain, Radix2EvaluationDomain};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Write};
-use ark_std::{format, marker::PhantomData, ops::*, vec};
+use ark_std::{marker::PhantomData, ops::*, vec};
 
 use ark_std::rand::RngCore;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::utils::{fingerprint, hash_to_field, interpolate, vanishing_poly};
+
+const CONSISTENCY_DST: &[u8] = b"SILENT-THRESHOLD-V01-CS01-SRS-CONSISTENCY";
+const FINGERPRINT_DST: &[u8] = b"SILENT-THRESHOLD-V01-CS01-SRS-FINGERPRINT";
 
 pub struct KZG10<E: Pairing, P: DenseUVPolynomial<E::ScalarField>> {
     _engine: PhantomData<E>,
     _poly: PhantomData<P>,
 }
 
+/// KZG10::setup split into chunks of powers, so that the caller can do other work in
+/// between, e.g. yield to the JS event loop. Gives the same params as setup for the same rng.
+pub struct SetupSteps<E: Pairing> {
+    powers_of_beta: Vec<E::ScalarField>,
+    window_size: usize,
+    g_table: Vec<Vec<E::G1Affine>>,
+    h_table: Vec<Vec<E::G2Affine>>,
+    powers_of_g: Vec<E::G1>,
+    powers_of_h: Vec<E::G2>,
+}
+
+impl<E: Pairing> SetupSteps<E> {
+    /// samples the trapdoor and builds the fixed-base tables
+    pub fn new<R: RngCore>(max_degree: usize, rng: &mut R) -> Result<Self, Error> {
+        if max_degree < 1 {
+            return Err(Error::DegreeIsZero);
+        }
+
+        let beta = E::ScalarField::rand(rng);
+        let g = E::G1::generator();
+        let h = E::G2::generator();
+
+        let mut powers_of_beta = vec![E::ScalarField::one()];
+
+        let mut cur = beta;
+        for _ in 0..max_degree {
+            powers_of_beta.push(cur);
+            cur *= &beta;
+        }
+
+        let window_size = FixedBase::get_mul_window_size(max_degree + 1);
+        let scalar_bits = E::ScalarField::MODULUS_BIT_SIZE as usize;
+
+        Ok(SetupSteps {
+            g_table: FixedBase::get_window_table(scalar_bits, window_size, g),
+            h_table: FixedBase::get_window_table(scalar_bits, window_size, h),
+            powers_of_g: Vec::with_capacity(max_degree + 1),
+            powers_of_h: Vec::with_capacity(max_degree + 1),
+            powers_of_beta,
+            window_size,
+        })
+    }
+
+    /// computes up to chunk more powers of g and h, returns true once all are done
+    pub fn step(&mut self, chunk: usize) -> bool {
+        let scalar_bits = E::ScalarField::MODULUS_BIT_SIZE as usize;
+        let start = self.powers_of_g.len();
+        let end = (start + chunk).min(self.powers_of_beta.len());
+        let betas = &self.powers_of_beta[start..end];
+
+        self.powers_of_g.extend(FixedBase::msm::<E::G1>(scalar_bits, self.window_size, &self.g_table, betas));
+        self.powers_of_h.extend(FixedBase::msm::<E::G2>(scalar_bits, self.window_size, &self.h_table, betas));
+        self.is_done()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.powers_of_g.len() == self.powers_of_beta.len()
+    }
+
+    /// fraction of the powers computed so far
+    pub fn progress(&self) -> f64 {
+        self.powers_of_g.len() as f64 / self.powers_of_beta.len() as f64
+    }
+
+    pub fn finish(self) -> UniversalParams<E> {
+        assert!(self.is_done(), "setup finished before all powers were computed");
+        UniversalParams::new(
+            E::G1::normalize_batch(&self.powers_of_g),
+            E::G2::normalize_batch(&self.powers_of_h),
+        )
+    }
+}
+
 pub struct UniversalParams<E: Pairing> {
     /// Group elements of the form `{ \beta^i G }`, where `i` ranges from 0 to `degree`.
     pub powers_of_g: Vec<E::G1Affine>,
     /// Group elements of the form `{ \beta^i H }`, where `i` ranges from 0 to `degree`.
     pub powers_of_h: Vec<E::G2Affine>,
+    /// hash of the powers, computed once in new
+    fingerprint: [u8; 32],
+}
+
+impl<E: Pairing> UniversalParams<E> {
+    /// params from their powers; the powers are not checked, see Valid::check
+    pub fn new(powers_of_g: Vec<E::G1Affine>, powers_of_h: Vec<E::G2Affine>) -> Self {
+        let mut params = UniversalParams {
+            powers_of_g,
+            powers_of_h,
+            fingerprint: [0; 32],
+        };
+        let mut bytes = Vec::new();
+        params.serialize_compressed(&mut bytes).unwrap();
+        params.fingerprint = fingerprint(FINGERPRINT_DST, &bytes);
+        params
+    }
+
+    /// the largest committee size these params support
+    pub fn degree(&self) -> usize {
+        self.powers_of_g.len() - 1
+    }
+
+    /// params for a smaller committee, keeping the powers of tau up to degree n.
+    /// lets one trusted setup serve every committee size up to the original degree
+    pub fn truncate(&self, n: usize) -> Result<Self, Error> {
+        if n < 1 {
+            return Err(Error::DegreeIsZero);
+        }
+        if n > self.degree() {
+            return Err(Error::DegreeTooLarge {
+                degree: n,
+                max_degree: self.degree(),
+            });
+        }
+
+        Ok(UniversalParams::new(
+            self.powers_of_g[..=n].to_vec(),
+            self.powers_of_h[..=n.min(self.powers_of_h.len() - 1)].to_vec(),
+        ))
+    }
+
+    /// commitments to the n Lagrange polynomials of the size n radix-2 domain,
+    /// i.e. g^{L_i(tau)}. n must be a power of two no larger than the degree.
+    /// since L_i(x) = 1/n sum_j omega^{-ij} x^j, this is an inverse FFT over g^{tau^j}
+    pub fn lagrange_basis_g1(&self, n: usize) -> Result<Vec<E::G1Affine>, Error> {
+        if n > self.degree() {
+            return Err(Error::DegreeTooLarge {
+                degree: n,
+                max_degree: self.degree(),
+            });
+        }
+        let domain = match Radix2EvaluationDomain::<E::ScalarField>::new(n) {
+            Some(domain) if domain.size() == n => domain,
+            _ => return Err(Error::InvalidDomainSize { size: n }),
+        };
+
+        let powers: Vec<E::G1> = self.powers_of_g[..n].iter().map(|g| g.into_group()).collect();
+        let basis = domain.ifft(&powers);
+        Ok(E::G1::normalize_batch(&basis))
+    }
+
+    /// stable content hash of the params, stored in ciphertexts made with them.
+    /// computed when the params are built or read, so it is cheap to compare
+    pub fn fingerprint(&self) -> [u8; 32] {
+        self.fingerprint
+    }
 }
 
 impl<E: Pairing> CanonicalSerialize for UniversalParams<E> {
@@ -64,7 +209,11 @@ impl<E: Pairing> CanonicalDeserialize for UniversalParams<E> {
     ) -> Result<Self, SerializationError> {
         let powers_of_g = Vec::<E::G1Affine>::deserialize_with_mode(&mut reader, compress, validate)?;
         let powers_of_h = Vec::<E::G2Affine>::deserialize_with_mode(&mut reader, compress, validate)?;
-        Ok(Self { powers_of_g, powers_of_h })
+        let params = Self::new(powers_of_g, powers_of_h);
+        if validate == ark_serialize::Validate::Yes {
+            params.check()?;
+        }
+        Ok(params)
     }
     
     fn deserialize_compressed<R: Read>(reader: R) -> Result<Self, SerializationError> {
@@ -85,9 +234,55 @@ impl<E: Pairing> CanonicalDeserialize for UniversalParams<E> {
 }
 
 impl<E: Pairing> Valid for UniversalParams<E> {
+    /// Checks that powers_of_g and powers_of_h are successive powers of the same tau,
+    /// starting at the generators. Both chains are checked with a single randomized
+    /// multi-pairing, using Fiat-Shamir randomness derived from the params:
+    /// e(sum rho^i g_{i+1}, h) = e(sum rho^i g_i, h_1) and
+    /// e(g_1, sum rho^{m+i} h_i) = e(g, sum rho^{m+i} h_{i+1})
     fn check(&self) -> Result<(), SerializationError> {
-        // Perform necessary checks to ensure that the deserialized data is valid
-        Ok(())
+        let g = &self.powers_of_g;
+        let h = &self.powers_of_h;
+
+        // at least tau^1 in both groups, and no G2 power without its G1 counterpart
+        if g.len() < 2 || h.len() < 2 || h.len() > g.len() {
+            return Err(SerializationError::InvalidData);
+        }
+        if g[0] != E::G1Affine::generator() || h[0] != E::G2Affine::generator() {
+            return Err(SerializationError::InvalidData);
+        }
+        if g[1].is_zero() {
+            return Err(SerializationError::InvalidData);
+        }
+
+        let mut msg = Vec::new();
+        self.serialize_compressed(&mut msg)?;
+        let rho: E::ScalarField = hash_to_field(CONSISTENCY_DST, &msg);
+
+        let m = g.len() - 1;
+        let mut rhos = Vec::with_capacity(m + h.len() - 1);
+        let mut cur = E::ScalarField::one();
+        for _ in 0..m + h.len() - 1 {
+            rhos.push(cur);
+            cur *= rho;
+        }
+        let (g_rhos, h_rhos) = rhos.split_at(m);
+
+        let g_cur = E::G1::msm(&g[..m], g_rhos).unwrap();
+        let g_next = E::G1::msm(&g[1..], g_rhos).unwrap();
+        let h_cur = E::G2::msm(&h[..h.len() - 1], h_rhos).unwrap();
+        let h_next = E::G2::msm(&h[1..], h_rhos).unwrap();
+
+        let consistent = E::multi_pairing(
+            [g_next, -g_cur, g[1].into_group(), -g[0].into_group()],
+            [h[0].into_group(), h[1].into_group(), h_cur, h_next],
+        )
+        .is_zero();
+
+        if consistent {
+            Ok(())
+        } else {
+            Err(SerializationError::InvalidData)
+        }
     }
 }
 
@@ -105,6 +300,23 @@ pub enum Error {
         /// The maximum number of powers provided in `Powers`.
         num_powers: usize,
     },
+
+    /// The requested degree exceeds the degree of the params.
+    DegreeTooLarge {
+        /// The requested degree.
+        degree: usize,
+        /// The degree of the params.
+        max_degree: usize,
+    },
+
+    /// The same point was given twice to a multi point opening.
+    RepeatedPoint,
+
+    /// The requested Lagrange basis is not over a power-of-two domain.
+    InvalidDomainSize {
+        /// The requested domain size.
+        size: usize,
+    },
 }
 
 impl<E, P> KZG10<E, P>
@@ -114,56 +326,25 @@ where
     for<'a, 'b> &'a P: Div<&'b P, Output = P>,
     for<'a, 'b> &'a P: Sub<&'b P, Output = P>,
 {
+    #[tracing::instrument(skip_all, fields(max_degree = max_degree))]
     pub fn setup<R: RngCore>(max_degree: usize, rng: &mut R) -> Result<UniversalParams<E>, Error> {
-        if max_degree < 1 {
-            return Err(Error::DegreeIsZero);
-        }
-
         //let setup_time = start_timer!(|| format!("KZG10::Setup with degree {}", max_degree));
-        let beta = E::ScalarField::rand(rng);
-        let g = E::G1::generator();
-        let h = E::G2::generator();
-
-        let mut powers_of_beta = vec![E::ScalarField::one()];
-
-        let mut cur = beta;
-        for _ in 0..max_degree {
-            powers_of_beta.push(cur);
-            cur *= &beta;
-        }
-
-        let window_size = FixedBase::get_mul_window_size(max_degree + 1);
-        let scalar_bits = E::ScalarField::MODULUS_BIT_SIZE as usize;
-
-        let g_table = FixedBase::get_window_table(scalar_bits, window_size, g);
-        let powers_of_g =
-            FixedBase::msm::<E::G1>(scalar_bits, window_size, &g_table, &powers_of_beta);
-
-        let h_table = FixedBase::get_window_table(scalar_bits, window_size, h);
-        let powers_of_h =
-            FixedBase::msm::<E::G2>(scalar_bits, window_size, &h_table, &powers_of_beta);
-
-        let powers_of_g = E::G1::normalize_batch(&powers_of_g);
-        let powers_of_h = E::G2::normalize_batch(&powers_of_h);
-
-        let pp = UniversalParams {
-            powers_of_g,
-            powers_of_h,
-        };
+        let mut steps = SetupSteps::new(max_degree, rng)?;
+        while !steps.step(max_degree + 1) {}
 
         //end_timer!(setup_time);
-        Ok(pp)
+        Ok(steps.finish())
     }
 
     pub fn commit_g1(params: &UniversalParams<E>, polynomial: &P) -> Result<E::G1Affine, Error> {
         let d = polynomial.degree();
         check_degree_is_too_large(d, params.powers_of_g.len())?;
 
-        let plain_coeffs = convert_to_bigints(polynomial.coeffs());
+        let (num_leading_zeros, plain_coeffs) = skip_leading_zeros_and_convert_to_bigints(polynomial);
 
-        let powers_of_g = &params.powers_of_g[..=d].to_vec();
+        let powers_of_g = &params.powers_of_g[num_leading_zeros..=d];
         //let msm_time = start_timer!(|| "MSM to compute commitment to plaintext poly");
-        let commitment = <E::G1 as VariableBaseMSM>::msm_bigint(&powers_of_g[..], &plain_coeffs);
+        let commitment = <E::G1 as VariableBaseMSM>::msm_bigint(powers_of_g, &plain_coeffs);
         //end_timer!(msm_time);
         Ok(commitment.into_affine())
     }
@@ -172,11 +353,11 @@ where
         let d = polynomial.degree();
         check_degree_is_too_large(d, params.powers_of_h.len())?;
 
-        let plain_coeffs = convert_to_bigints(polynomial.coeffs());
+        let (num_leading_zeros, plain_coeffs) = skip_leading_zeros_and_convert_to_bigints(polynomial);
 
-        let powers_of_h = &params.powers_of_h[..=d].to_vec();
+        let powers_of_h = &params.powers_of_h[num_leading_zeros..=d];
         //let msm_time = start_timer!(|| "MSM to compute commitment to plaintext poly");
-        let commitment = <E::G2 as VariableBaseMSM>::msm_bigint(&powers_of_h[..], &plain_coeffs);
+        let commitment = <E::G2 as VariableBaseMSM>::msm_bigint(powers_of_h, &plain_coeffs);
         //end_timer!(msm_time);
 
         Ok(commitment.into_affine())
@@ -196,6 +377,94 @@ where
 
         Self::commit_g1(params, &witness_polynomial)
     }
+
+    /// checks that proof opens commitment to value at point:
+    /// e(C - v*g, h) = e(proof, h^tau - point*h)
+    pub fn verify(
+        params: &UniversalParams<E>,
+        commitment: &E::G1Affine,
+        point: &E::ScalarField,
+        value: &E::ScalarField,
+        proof: &E::G1Affine,
+    ) -> bool {
+        let g = params.powers_of_g[0];
+        let h = params.powers_of_h[0];
+        let lhs = commitment.into_group() - g * value;
+        let rhs = params.powers_of_h[1].into_group() - h * point;
+        E::multi_pairing([lhs, -proof.into_group()], [h.into_group(), rhs]).is_zero()
+    }
+
+    /// checks many single point openings at once with a random linear combination r_i:
+    /// e(sum r_i (C_i - v_i*g + z_i*proof_i), h) = e(sum r_i proof_i, h^tau)
+    pub fn batch_verify<R: RngCore>(
+        params: &UniversalParams<E>,
+        commitments: &[E::G1Affine],
+        points: &[E::ScalarField],
+        values: &[E::ScalarField],
+        proofs: &[E::G1Affine],
+        rng: &mut R,
+    ) -> bool {
+        let k = commitments.len();
+        if points.len() != k || values.len() != k || proofs.len() != k {
+            return false;
+        }
+
+        let g = params.powers_of_g[0];
+        let mut total_c = E::G1::zero();
+        let mut total_w = E::G1::zero();
+        let mut total_v = E::ScalarField::zero();
+        for i in 0..k {
+            let r = E::ScalarField::rand(rng);
+            total_c += (commitments[i] + proofs[i] * points[i]) * r;
+            total_w += proofs[i] * r;
+            total_v += values[i] * r;
+        }
+        total_c -= g * total_v;
+
+        E::multi_pairing(
+            [total_c, -total_w],
+            [params.powers_of_h[0], params.powers_of_h[1]],
+        )
+        .is_zero()
+    }
+
+    /// opens polynomial at all of points with a single group element,
+    /// the commitment to (p(x) - I(x)) / Z(x) where I interpolates p on points
+    /// and Z vanishes on them. points must be distinct
+    pub fn compute_multi_point_opening_proof(
+        params: &UniversalParams<E>,
+        polynomial: &P,
+        points: &[E::ScalarField],
+    ) -> Result<E::G1Affine, Error> {
+        let values: Vec<E::ScalarField> = points.iter().map(|z| polynomial.evaluate(z)).collect();
+        let interp = P::from_coefficients_vec(interpolate(points, &values).ok_or(Error::RepeatedPoint)?.coeffs);
+        let vanishing = P::from_coefficients_vec(vanishing_poly(points).coeffs);
+        let witness_polynomial = (polynomial - &interp).div(&vanishing);
+
+        Self::commit_g1(params, &witness_polynomial)
+    }
+
+    /// checks a multi point opening: e(C - [I(tau)]_1, h) = e(proof, [Z(tau)]_2).
+    /// needs params with at least points.len() powers of h beyond the generator
+    pub fn verify_multi_point(
+        params: &UniversalParams<E>,
+        commitment: &E::G1Affine,
+        points: &[E::ScalarField],
+        values: &[E::ScalarField],
+        proof: &E::G1Affine,
+    ) -> Result<bool, Error> {
+        if points.len() != values.len() {
+            return Ok(false);
+        }
+
+        let interp = P::from_coefficients_vec(interpolate(points, values).ok_or(Error::RepeatedPoint)?.coeffs);
+        let vanishing = P::from_coefficients_vec(vanishing_poly(points).coeffs);
+        let interp_g1 = Self::commit_g1(params, &interp)?;
+        let vanishing_g2 = Self::commit_g2(params, &vanishing)?;
+
+        let lhs = commitment.into_group() - interp_g1;
+        Ok(E::multi_pairing([lhs, -proof.into_group()], [params.powers_of_h[0], vanishing_g2]).is_zero())
+    }
 }
 
 fn skip_leading_zeros_and_convert_to_bigints<F: PrimeField, P: DenseUVPolynomial<F>>(
@@ -226,3 +495,129 @@ fn check_degree_is_too_large(degree: usize, num_powers: usize) -> Result<(), Err
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_poly::{univariate::DensePolynomial, Polynomial};
+
+    type E = ark_bls12_381::Bls12_381;
+    type UniPoly381 = DensePolynomial<<E as Pairing>::ScalarField>;
+
+    #[test]
+    fn test_params_check() {
+        let mut rng = ark_std::test_rng();
+        let params = KZG10::<E, UniPoly381>::setup(16, &mut rng).unwrap();
+        assert!(params.check().is_ok());
+
+        // chunked setup gives the same params for the same rng
+        let mut steps = SetupSteps::<E>::new(16, &mut ark_std::test_rng()).unwrap();
+        while !steps.step(5) {
+            assert!(steps.progress() < 1.0);
+        }
+        let chunked = steps.finish();
+        assert_eq!(chunked.powers_of_g, params.powers_of_g);
+        assert_eq!(chunked.powers_of_h, params.powers_of_h);
+
+        let mut bytes = Vec::new();
+        params.serialize_compressed(&mut bytes).unwrap();
+        assert!(UniversalParams::<E>::deserialize_compressed(&bytes[..]).is_ok());
+
+        // swapping two powers keeps every point valid but breaks the structure
+        let mut tampered = UniversalParams::<E>::new(params.powers_of_g.clone(), params.powers_of_h.clone());
+        tampered.powers_of_g.swap(3, 4);
+        let mut bytes = Vec::new();
+        tampered.serialize_compressed(&mut bytes).unwrap();
+        assert!(UniversalParams::<E>::deserialize_compressed(&bytes[..]).is_err());
+        assert!(UniversalParams::<E>::deserialize_compressed_unchecked(&bytes[..]).is_ok());
+
+        let mut tampered = UniversalParams::<E>::new(params.powers_of_g.clone(), params.powers_of_h.clone());
+        tampered.powers_of_h[7] = tampered.powers_of_h[6];
+        assert!(tampered.check().is_err());
+
+        // params built on a different generator
+        let mut other = UniversalParams::<E>::new(params.powers_of_g.clone(), params.powers_of_h.clone());
+        other.powers_of_g[0] = params.powers_of_g[1];
+        assert!(other.check().is_err());
+
+        let short = UniversalParams::<E>::new(params.powers_of_g[..4].to_vec(), params.powers_of_h[..5].to_vec());
+        assert!(short.check().is_err());
+    }
+
+    #[test]
+    fn test_truncate_and_lagrange_basis() {
+        let mut rng = ark_std::test_rng();
+        let params = KZG10::<E, UniPoly381>::setup(16, &mut rng).unwrap();
+
+        let small = params.truncate(8).unwrap();
+        assert_eq!(small.degree(), 8);
+        assert!(small.check().is_ok());
+        assert_ne!(small.fingerprint(), params.fingerprint());
+        assert!(matches!(params.truncate(0), Err(Error::DegreeIsZero)));
+        assert!(matches!(
+            params.truncate(17),
+            Err(Error::DegreeTooLarge { degree: 17, max_degree: 16 })
+        ));
+
+        // the basis matches committing to the Lagrange polynomials directly,
+        // and does not depend on which truncation it was derived from
+        for n in [1, 2, 4, 8] {
+            let basis = params.lagrange_basis_g1(n).unwrap();
+            assert_eq!(basis, small.lagrange_basis_g1(n).unwrap());
+            for (i, li) in basis.iter().enumerate() {
+                let expected = KZG10::<E, UniPoly381>::commit_g1(&params, &crate::utils::lagrange_poly(n, i)).unwrap();
+                assert_eq!(*li, expected);
+            }
+        }
+        assert_eq!(params.lagrange_basis_g1(16).unwrap().len(), 16);
+
+        assert!(matches!(params.lagrange_basis_g1(6), Err(Error::InvalidDomainSize { size: 6 })));
+        assert!(matches!(small.lagrange_basis_g1(16), Err(Error::DegreeTooLarge { .. })));
+    }
+
+    #[test]
+    fn test_openings() {
+        type F = <E as Pairing>::ScalarField;
+        type Kzg = KZG10<E, UniPoly381>;
+
+        let mut rng = ark_std::test_rng();
+        let params = Kzg::setup(16, &mut rng).unwrap();
+
+        // single point openings, including a polynomial with a zero constant term
+        let mut polys: Vec<UniPoly381> = (0..4).map(|_| UniPoly381::rand(10, &mut rng)).collect();
+        polys[1].coeffs[0] = F::zero();
+        polys[1].coeffs[1] = F::zero();
+        let commitments: Vec<_> = polys.iter().map(|p| Kzg::commit_g1(&params, p).unwrap()).collect();
+        let points: Vec<F> = (0..4).map(|_| F::rand(&mut rng)).collect();
+        let values: Vec<F> = polys.iter().zip(points.iter()).map(|(p, z)| p.evaluate(z)).collect();
+        let proofs: Vec<_> = polys
+            .iter()
+            .zip(points.iter())
+            .map(|(p, z)| Kzg::compute_opening_proof(&params, p, z).unwrap())
+            .collect();
+
+        for i in 0..4 {
+            assert!(Kzg::verify(&params, &commitments[i], &points[i], &values[i], &proofs[i]));
+            assert!(!Kzg::verify(&params, &commitments[i], &points[i], &(values[i] + F::one()), &proofs[i]));
+        }
+        assert!(Kzg::batch_verify(&params, &commitments, &points, &values, &proofs, &mut rng));
+
+        let mut bad_values = values.clone();
+        bad_values[2] += F::one();
+        assert!(!Kzg::batch_verify(&params, &commitments, &points, &bad_values, &proofs, &mut rng));
+
+        // one proof for several points
+        let points: Vec<F> = (0..5).map(|_| F::rand(&mut rng)).collect();
+        let values: Vec<F> = points.iter().map(|z| polys[0].evaluate(z)).collect();
+        let proof = Kzg::compute_multi_point_opening_proof(&params, &polys[0], &points).unwrap();
+        assert!(Kzg::verify_multi_point(&params, &commitments[0], &points, &values, &proof).unwrap());
+        assert!(!Kzg::verify_multi_point(&params, &commitments[1], &points, &values, &proof).unwrap());
+        assert!(!Kzg::verify_multi_point(&params, &commitments[0], &points[1..], &values[1..], &proof).unwrap());
+
+        let repeated = [points[0], points[0]];
+        assert!(matches!(
+            Kzg::compute_multi_point_opening_proof(&params, &polys[0], &repeated),
+            Err(Error::RepeatedPoint)
+        ));
+    }
+}
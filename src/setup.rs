@@ -1,62 +1,208 @@
 use ark_ec::pairing::PairingOutput;
-use ark_ec::{pairing::Pairing, Group};
+use ark_ec::{pairing::Pairing, CurveGroup, Group};
 use ark_poly::DenseUVPolynomial;
 use ark_poly::{domain::EvaluationDomain, univariate::DensePolynomial, Radix2EvaluationDomain};
 use ark_serialize::*;
-use ark_std::{rand::RngCore, One, UniformRand, Zero};
+use ark_std::{cfg_into_iter, cfg_iter, rand::RngCore, One, UniformRand, Zero};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::fmt;
 use std::ops::{Mul, Sub};
-use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
-use wasm_bindgen::convert::{IntoWasmAbi, FromWasmAbi};
-use wasm_bindgen::describe::WasmDescribe;
-use serde_wasm_bindgen;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
+use crate::bls::{HashToG2, POP_DST};
 use crate::encryption::Ciphertext;
 use crate::kzg::{UniversalParams, KZG10};
-use crate::utils::lagrange_poly;
+use crate::utils::{checked, fingerprint, lagrange_poly};
 
-#[derive(CanonicalSerialize, CanonicalDeserialize, Clone, Serialize, Deserialize)]
+const FINGERPRINT_DST: &[u8] = b"SILENT-THRESHOLD-V01-CS01-AGGREGATE-KEY-FINGERPRINT";
+
+/// A party's secret scalar. It is wiped on drop, redacted from Debug output, and
+/// only leaves the type through export_secret_bytes.
 pub struct SecretKey<E: Pairing> {
-    pub sk: E::ScalarField,
+    sk: E::ScalarField,
 }
 
+impl<E: Pairing> fmt::Debug for SecretKey<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretKey(<redacted>)")
+    }
+}
+
+impl<E: Pairing> Zeroize for SecretKey<E> {
+    fn zeroize(&mut self) {
+        self.sk.zeroize();
+    }
+}
+
+impl<E: Pairing> Drop for SecretKey<E> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<E: Pairing> ZeroizeOnDrop for SecretKey<E> {}
+
 #[derive(CanonicalSerialize, CanonicalDeserialize, Clone, Serialize, Deserialize)]
 pub struct PublicKey<E: Pairing> {
     pub id: usize,
+    /// degree of the params the hints were computed with
+    pub degree: usize,
     pub bls_pk: E::G1,
     pub sk_li: E::G1,
     pub sk_li_minus0: E::G1,
     pub sk_li_by_z: Vec<E::G1>,
     pub sk_li_by_tau: E::G1,
+    /// proof of possession of the secret key behind bls_pk for this id
+    pub pop: E::G2,
 }
 
 pub struct AggregateKey<E: Pairing> {
     pub pk: Vec<PublicKey<E>>,
+    /// degree of the params the committee was built for
+    pub degree: usize,
     pub agg_sk_li_by_z: Vec<E::G1>,
     pub ask: E::G1,
     pub z_g2: E::G2,
     pub h_minus1: E::G2,
     pub e_gh: PairingOutput<E>,
+    /// affine copies of the hints aggregated on every decryption
+    pub bases: HintBases<E>,
+    /// hash of the committee, computed once in from_parts
+    fingerprint: [u8; 32],
+}
+
+/// The per-party hints read by agg_dec, batch-normalized once so that
+/// decryptions can feed them to the MSM without converting each point again.
+/// Indexed by party like pk.
+pub struct HintBases<E: Pairing> {
+    pub bls_pk: Vec<E::G1Affine>,
+    pub sk_li_by_tau: Vec<E::G1Affine>,
+    pub sk_li_minus0: Vec<E::G1Affine>,
+    pub agg_sk_li_by_z: Vec<E::G1Affine>,
+}
+
+impl<E: Pairing> HintBases<E> {
+    pub fn new(pk: &[PublicKey<E>], agg_sk_li_by_z: &[E::G1]) -> Self {
+        let n = pk.len();
+        let mut points = Vec::with_capacity(4 * n);
+        points.extend(pk.iter().map(|pki| pki.bls_pk));
+        points.extend(pk.iter().map(|pki| pki.sk_li_by_tau));
+        points.extend(pk.iter().map(|pki| pki.sk_li_minus0));
+        points.extend_from_slice(agg_sk_li_by_z);
+
+        let mut affine = E::G1::normalize_batch(&points);
+        let agg_sk_li_by_z = affine.split_off(3 * n);
+        let sk_li_minus0 = affine.split_off(2 * n);
+        let sk_li_by_tau = affine.split_off(n);
+
+        HintBases {
+            bls_pk: affine,
+            sk_li_by_tau,
+            sk_li_minus0,
+            agg_sk_li_by_z,
+        }
+    }
 }
 
 impl<E: Pairing> PublicKey<E> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id: usize,
+        degree: usize,
         bls_pk: E::G1,
         sk_li: E::G1,
         sk_li_minus0: E::G1,
         sk_li_by_z: Vec<E::G1>,
         sk_li_by_tau: E::G1,
+        pop: E::G2,
     ) -> Self {
         PublicKey {
             id,
+            degree,
             bls_pk,
             sk_li,
             sk_li_minus0,
             sk_li_by_z,
             sk_li_by_tau,
+            pop,
         }
     }
+
+    /// checks the proof of possession of the secret key behind bls_pk for this id
+    pub fn verify_possession(&self) -> bool
+    where
+        E: HashToG2,
+    {
+        let hm = E::hash_to_g2(&pop_message::<E>(&self.bls_pk, self.id), POP_DST);
+        E::multi_pairing([-E::G1::generator(), self.bls_pk], [self.pop, hm]).is_zero()
+    }
+
+    /// checks that partial is SecretKey::partial_decryption of ct under the key behind bls_pk,
+    /// i.e. e(g, partial) == e(bls_pk, gamma_g2)
+    pub fn verify_partial_decryption(&self, ct: &Ciphertext<E>, partial: &E::G2) -> bool {
+        E::multi_pairing([-E::G1::generator(), self.bls_pk], [*partial, ct.gamma_g2]).is_zero()
+    }
+}
+
+/// message signed by a proof of possession, binding the public key to its slot id
+fn pop_message<E: Pairing>(bls_pk: &E::G1, id: usize) -> Vec<u8> {
+    let mut msg = Vec::new();
+    bls_pk.serialize_compressed(&mut msg).unwrap();
+    (id as u64).serialize_compressed(&mut msg).unwrap();
+    msg
+}
+
+#[derive(Debug)]
+pub enum Error {
+    /// The proof of possession of a public key did not verify.
+    InvalidProofOfPossession {
+        /// The slot id of the offending public key.
+        id: usize,
+    },
+
+    /// A public key was generated for another slot than the one it is placed in.
+    SlotMismatch {
+        /// The position of the public key in the committee.
+        position: usize,
+        /// The slot id the key and its proof of possession were made for.
+        id: usize,
+    },
+
+    /// A public key was computed with params of a different degree.
+    DegreeMismatch {
+        /// The slot id of the offending public key.
+        id: usize,
+        /// The degree recorded in the public key.
+        degree: usize,
+        /// The degree of the params passed in.
+        expected: usize,
+    },
+
+    /// A public key's hints were computed for a committee of another size.
+    HintCountMismatch {
+        /// The slot id of the offending public key.
+        id: usize,
+        /// The number of sk_li_by_z hints in the public key.
+        hints: usize,
+        /// The number of public keys.
+        n: usize,
+    },
+
+    /// The bytes are not a canonical encoding of the committee's public keys.
+    InvalidEncoding(SerializationError),
+
+    /// A ciphertext's proof does not verify against the label and the aggregate key.
+    InvalidCiphertext,
+
+    /// The committee size differs from the degree of the params.
+    CommitteeSizeMismatch {
+        /// The number of public keys.
+        n: usize,
+        /// The degree of the params passed in.
+        degree: usize,
+    },
 }
 
 impl<E: Pairing> SecretKey<E> {
@@ -66,33 +212,70 @@ impl<E: Pairing> SecretKey<E> {
         }
     }
 
+    pub fn from_scalar(sk: E::ScalarField) -> Self {
+        SecretKey { sk }
+    }
+
+    /// the 32 byte canonical encoding of the scalar, wiped when the returned buffer is dropped
+    pub fn export_secret_bytes(&self) -> Zeroizing<Vec<u8>> {
+        let mut bytes = Zeroizing::new(Vec::with_capacity(self.sk.uncompressed_size()));
+        self.sk.serialize_uncompressed(&mut *bytes).unwrap();
+        bytes
+    }
+
+    /// reads the encoding written by export_secret_bytes, rejecting trailing bytes
+    pub fn from_secret_bytes(bytes: &[u8]) -> Result<Self, SerializationError> {
+        Ok(SecretKey { sk: checked(bytes)? })
+    }
+
+    /// the scalar itself, for the arithmetic in this crate
+    pub(crate) fn scalar(&self) -> E::ScalarField {
+        self.sk
+    }
+
     pub fn nullify(&mut self) {
         self.sk = E::ScalarField::one()
     }
 
-    pub fn get_pk(&self, id: usize, params: &UniversalParams<E>, n: usize) -> PublicKey<E> {
+    /// g^sk, the BLS public key that get_pk publishes
+    pub fn bls_pk(&self) -> E::G1 {
+        E::G1::generator() * self.sk
+    }
+
+    /// signs the public key and slot id with a domain separated BLS signature
+    pub fn prove_possession(&self, id: usize) -> E::G2
+    where
+        E: HashToG2,
+    {
+        E::hash_to_g2(&pop_message::<E>(&self.bls_pk(), id), POP_DST) * self.sk
+    }
+
+    #[tracing::instrument(name = "hints", skip_all, fields(id = id, n = n))]
+    pub fn get_pk(&self, id: usize, params: &UniversalParams<E>, n: usize) -> PublicKey<E>
+    where
+        E: HashToG2,
+    {
         let domain = Radix2EvaluationDomain::<E::ScalarField>::new(n).unwrap();
 
         let li = lagrange_poly(n, id);
 
-        let mut sk_li_by_z = vec![];
-        for j in 0..n {
-            let num = if id == j {
-                li.clone().mul(&li).sub(&li)
-            } else {
-                let l_j = lagrange_poly(n, j);
-                l_j.mul(&li)
-            };
+        let sk_li_by_z: Vec<E::G1> = cfg_into_iter!(0..n)
+            .map(|j| {
+                let num = if id == j {
+                    li.clone().mul(&li).sub(&li)
+                } else {
+                    let l_j = lagrange_poly(n, j);
+                    l_j.mul(&li)
+                };
 
-            let f = num.divide_by_vanishing_poly(domain).unwrap().0;
-            let sk_times_f = &f * self.sk;
+                let f = num.divide_by_vanishing_poly(domain).unwrap().0;
+                let sk_times_f = &f * self.sk;
 
-            let com = KZG10::commit_g1(params, &sk_times_f)
-                .expect("commitment failed")
-                .into();
-
-            sk_li_by_z.push(com);
-        }
+                KZG10::commit_g1(params, &sk_times_f)
+                    .expect("commitment failed")
+                    .into()
+            })
+            .collect();
 
         let f = DensePolynomial::from_coefficients_vec(li.coeffs[1..].to_vec());
         let sk_times_f = &f * self.sk;
@@ -112,22 +295,74 @@ impl<E: Pairing> SecretKey<E> {
 
         PublicKey {
             id,
-            bls_pk: E::G1::generator() * self.sk,
+            degree: params.degree(),
+            bls_pk: self.bls_pk(),
             sk_li,
             sk_li_minus0,
             sk_li_by_z,
             sk_li_by_tau,
+            pop: self.prove_possession(id),
         }
     }
 
-    pub fn partial_decryption(&self, ct: &Ciphertext<E>) -> E::G2 {
-        ct.gamma_g2 * self.sk
+    /// gamma_g2 * sk, only for a ciphertext whose proof of knowledge of gamma verifies
+    /// against label and agg_key. the same key signs (bls::sign), so answering for an
+    /// arbitrary gamma_g2 such as H(m) would hand out a signature on m
+    pub fn partial_decryption(
+        &self,
+        ct: &Ciphertext<E>,
+        label: &[u8],
+        agg_key: &AggregateKey<E>,
+        params: &UniversalParams<E>,
+    ) -> Result<E::G2, Error> {
+        if !ct.verify(label, agg_key, params) {
+            return Err(Error::InvalidCiphertext);
+        }
+        Ok(ct.gamma_g2 * self.sk)
     }
 }
 
 impl<E: Pairing> AggregateKey<E> {
-    pub fn new(pk: Vec<PublicKey<E>>, params: &UniversalParams<E>) -> Self {
+    /// aggregates the committee's public keys, which must be ordered by slot id, rejecting
+    /// any key without a valid proof of possession
+    #[tracing::instrument(name = "aggregate", skip_all, fields(n = pk.len()))]
+    pub fn new(pk: Vec<PublicKey<E>>, params: &UniversalParams<E>) -> Result<Self, Error>
+    where
+        E: HashToG2,
+    {
+        // the hints and the proof of possession are only meaningful in the slot they were made for
+        if let Some((position, pki)) = pk.iter().enumerate().find(|(i, pki)| pki.id != *i) {
+            return Err(Error::SlotMismatch { position, id: pki.id });
+        }
+
+        let valid: Vec<bool> = cfg_iter!(pk).map(|pki| pki.verify_possession()).collect();
+        if let Some(bad) = pk.iter().zip(valid).find(|(_, valid)| !valid) {
+            return Err(Error::InvalidProofOfPossession { id: bad.0.id });
+        }
+
+        // hints from a different SRS would aggregate into garbage
+        let degree = params.degree();
+        if let Some(bad) = pk.iter().find(|pki| pki.degree != degree) {
+            return Err(Error::DegreeMismatch {
+                id: bad.id,
+                degree: bad.degree,
+                expected: degree,
+            });
+        }
+
+        // the powers past tau^n are not covered by the hints, and the security argument
+        // assumes an SRS of exactly the committee size; params.truncate(n) gives one
         let n = pk.len();
+        if n != degree {
+            return Err(Error::CommitteeSizeMismatch { n, degree });
+        }
+        if let Some(bad) = pk.iter().find(|pki| pki.sk_li_by_z.len() != n) {
+            return Err(Error::HintCountMismatch {
+                id: bad.id,
+                hints: bad.sk_li_by_z.len(),
+                n,
+            });
+        }
         let h_minus1 = params.powers_of_h[0] * (-E::ScalarField::one());
         let z_g2 = params.powers_of_h[n] + h_minus1;
 
@@ -136,72 +371,95 @@ impl<E: Pairing> AggregateKey<E> {
             ask += pki.sk_li;
         }
 
-        let mut agg_sk_li_by_z = vec![];
-        for i in 0..n {
-            let mut agg_sk_li_by_zi = E::G1::zero();
-            for pkj in pk.iter() {
-                agg_sk_li_by_zi += pkj.sk_li_by_z[i];
-            }
-            agg_sk_li_by_z.push(agg_sk_li_by_zi);
-        }
+        let agg_sk_li_by_z: Vec<E::G1> = cfg_into_iter!(0..n)
+            .map(|i| pk.iter().map(|pkj| pkj.sk_li_by_z[i]).sum())
+            .collect();
 
-        AggregateKey {
+        let e_gh = E::pairing(params.powers_of_g[0], params.powers_of_h[0]);
+        Ok(Self::from_parts(pk, degree, agg_sk_li_by_z, ask, z_g2, h_minus1, e_gh))
+    }
+
+    /// reads the encoding written by CanonicalSerialize, the committee's public keys, and
+    /// aggregates them again with new, so a key read from bytes passes the same checks
+    /// and has the same derived values as a freshly aggregated one
+    pub fn from_bytes(bytes: &[u8], params: &UniversalParams<E>) -> Result<Self, Error>
+    where
+        E: HashToG2,
+    {
+        let pk = checked::<Vec<PublicKey<E>>>(bytes).map_err(Error::InvalidEncoding)?;
+        Self::new(pk, params)
+    }
+
+    /// assembles a key from already aggregated parts, building the hint bases and the
+    /// fingerprint. nothing is checked, so the parts must come from new, e.g. when the
+    /// hints of a large committee are made up for a benchmark
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_parts(
+        pk: Vec<PublicKey<E>>,
+        degree: usize,
+        agg_sk_li_by_z: Vec<E::G1>,
+        ask: E::G1,
+        z_g2: E::G2,
+        h_minus1: E::G2,
+        e_gh: PairingOutput<E>,
+    ) -> Self {
+        let bases = HintBases::new(&pk, &agg_sk_li_by_z);
+        let mut key = AggregateKey {
             pk,
+            degree,
             agg_sk_li_by_z,
             ask,
             z_g2,
             h_minus1,
-            e_gh: E::pairing(params.powers_of_g[0], params.powers_of_h[0]),
-        }
+            e_gh,
+            bases,
+            fingerprint: [0; 32],
+        };
+        key.fingerprint = key.compute_fingerprint();
+        key
     }
-}
-
-fn serialize_scalar<E: Pairing>(scalar: &E::ScalarField) -> Vec<u8> {
-    let mut bytes = vec![];
-    scalar.serialize_uncompressed(&mut bytes).unwrap();
-    bytes
-}
 
-fn deserialize_scalar<E: Pairing>(bytes: &[u8]) -> E::ScalarField {
-    E::ScalarField::deserialize_uncompressed(bytes).unwrap()
-}
-
-// Implement WasmDescribe for SecretKey
-impl<E: Pairing> WasmDescribe for SecretKey<E> {
-    fn describe() {
-        use wasm_bindgen::describe::*;
-        inform(wasm_bindgen::describe::RUST_STRUCT);
-        inform(wasm_bindgen::describe::U8);
+    /// stable content hash of the committee, stored in ciphertexts encrypted to it.
+    /// computed when the key is built or read, so it is cheap to compare
+    pub fn fingerprint(&self) -> [u8; 32] {
+        self.fingerprint
     }
-}
 
-// Implement IntoWasmAbi for SecretKey
-impl<E: Pairing> IntoWasmAbi for SecretKey<E> {
-    type Abi = <JsValue as IntoWasmAbi>::Abi;
-
-    fn into_abi(self) -> Self::Abi {
-        let bytes = serialize_scalar::<E>(&self.sk);
-        let js_value = serde_wasm_bindgen::to_value(&bytes).unwrap();
-        js_value.into_abi()
+    /// covers everything agg_dec reads; the hints are hashed through the affine bases,
+    /// which serialize without a field inversion per point
+    fn compute_fingerprint(&self) -> [u8; 32] {
+        let mut bytes = Vec::new();
+        for pki in self.pk.iter() {
+            pki.id.serialize_compressed(&mut bytes).unwrap();
+            pki.degree.serialize_compressed(&mut bytes).unwrap();
+        }
+        self.degree.serialize_compressed(&mut bytes).unwrap();
+        self.bases.bls_pk.serialize_compressed(&mut bytes).unwrap();
+        self.bases.sk_li_by_tau.serialize_compressed(&mut bytes).unwrap();
+        self.bases.sk_li_minus0.serialize_compressed(&mut bytes).unwrap();
+        self.bases.agg_sk_li_by_z.serialize_compressed(&mut bytes).unwrap();
+        self.ask.serialize_compressed(&mut bytes).unwrap();
+        self.z_g2.serialize_compressed(&mut bytes).unwrap();
+        self.h_minus1.serialize_compressed(&mut bytes).unwrap();
+        self.e_gh.serialize_compressed(&mut bytes).unwrap();
+        fingerprint(FINGERPRINT_DST, &bytes)
     }
 }
 
-// Implement FromWasmAbi for SecretKey
-impl<E: Pairing> FromWasmAbi for SecretKey<E> {
-    type Abi = <JsValue as FromWasmAbi>::Abi;
-
-    unsafe fn from_abi(js: Self::Abi) -> Self {
-        let js_value = JsValue::from_abi(js);
-        let bytes: Vec<u8> = serde_wasm_bindgen::from_value(js_value).unwrap();
-        SecretKey {
-            sk: deserialize_scalar::<E>(&bytes),
-        }
+// everything but the public keys is derived from them and the params, so only the public
+// keys are encoded, and AggregateKey::from_bytes aggregates them again
+impl<E: Pairing> CanonicalSerialize for AggregateKey<E> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.pk.serialize_with_mode(writer, compress)
     }
-}
 
-#[wasm_bindgen(start)]
-pub fn main() {
-    console_error_panic_hook::set_once();
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.pk.serialized_size(compress)
+    }
 }
 
 #[cfg(test)]
@@ -222,10 +480,135 @@ mod tests {
 
         for i in 0..n {
             sk.push(SecretKey::<E>::new(&mut rng));
-            pk.push(sk[i].get_pk(0, &params, n))
+            pk.push(sk[i].get_pk(i, &params, n))
         }
 
-        let _ak = AggregateKey::<E>::new(pk, &params);
+        let ak = AggregateKey::<E>::new(pk, &params).unwrap();
+
+        // the canonical encoding round-trips, derived values included
+        let mut bytes = Vec::new();
+        ak.serialize_uncompressed(&mut bytes).unwrap();
+        let decoded = AggregateKey::<E>::from_bytes(&bytes, &params).unwrap();
+        assert_eq!(decoded.fingerprint(), ak.fingerprint());
+        assert!(matches!(
+            AggregateKey::<E>::from_bytes(&bytes[..bytes.len() - 1], &params),
+            Err(Error::InvalidEncoding(_))
+        ));
+
+        // and what is read is checked like a fresh committee
+        let mut swapped = ak.pk.clone();
+        swapped.swap(1, 2);
+        let mut bytes = Vec::new();
+        swapped.serialize_uncompressed(&mut bytes).unwrap();
+        assert!(matches!(
+            AggregateKey::<E>::from_bytes(&bytes, &params),
+            Err(Error::SlotMismatch { position: 1, id: 2 })
+        ));
+
+        let mut short = ak.pk.clone();
+        short[3].sk_li_by_z.pop();
+        let mut bytes = Vec::new();
+        short.serialize_uncompressed(&mut bytes).unwrap();
+        assert!(matches!(
+            AggregateKey::<E>::from_bytes(&bytes, &params),
+            Err(Error::HintCountMismatch { id: 3, hints: 3, n: 4 })
+        ));
+    }
+
+    #[test]
+    fn test_secret_key_handling() {
+        let mut rng = ark_std::test_rng();
+        let sk = SecretKey::<E>::new(&mut rng);
+        assert_eq!(format!("{:?}", sk), "SecretKey(<redacted>)");
+
+        let bytes = sk.export_secret_bytes();
+        assert_eq!(bytes.len(), 32);
+        let imported = SecretKey::<E>::from_secret_bytes(&bytes).unwrap();
+        assert_eq!(imported.bls_pk(), sk.bls_pk());
+        assert!(SecretKey::<E>::from_secret_bytes(&bytes[1..]).is_err());
+        assert!(SecretKey::<E>::from_secret_bytes(&[bytes.as_slice(), &[0]].concat()).is_err());
+
+        let mut wiped = imported;
+        wiped.zeroize();
+        assert!(wiped.scalar().is_zero());
+    }
+
+    #[test]
+    fn test_proof_of_possession() {
+        let mut rng = ark_std::test_rng();
+        let n = 4;
+        let params = KZG10::<E, UniPoly381>::setup(n, &mut rng).unwrap();
+
+        let mut pk: Vec<PublicKey<E>> = Vec::new();
+        for i in 0..n {
+            let sk = SecretKey::<E>::new(&mut rng);
+            pk.push(sk.get_pk(i, &params, n));
+            assert!(pk[i].verify_possession());
+        }
+
+        // a proof for one slot does not transfer to another
+        let mut moved = pk[1].clone();
+        moved.id = 2;
+        assert!(!moved.verify_possession());
+
+        // and a correctly signed key cannot be placed in another slot
+        let mut swapped = pk.clone();
+        swapped.swap(1, 2);
+        assert!(swapped[1].verify_possession());
+        assert!(matches!(
+            AggregateKey::<E>::new(swapped, &params),
+            Err(Error::SlotMismatch { position: 1, id: 2 })
+        ));
+
+        // a rogue key without knowledge of its secret is rejected
+        pk[3].bls_pk = pk[3].bls_pk - pk[1].bls_pk;
+        match AggregateKey::<E>::new(pk, &params) {
+            Err(Error::InvalidProofOfPossession { id }) => assert_eq!(id, 3),
+            _ => panic!("expected an invalid proof of possession"),
+        }
+    }
+
+    #[test]
+    fn test_truncated_params() {
+        let mut rng = ark_std::test_rng();
+        let n = 4;
+        let big = KZG10::<E, UniPoly381>::setup(4 * n, &mut rng).unwrap();
+        let params = big.truncate(n).unwrap();
+
+        let sk: Vec<SecretKey<E>> = (0..n).map(|_| SecretKey::<E>::new(&mut rng)).collect();
+        let mut pk: Vec<PublicKey<E>> = (0..n).map(|i| sk[i].get_pk(i, &params, n)).collect();
+        assert!(pk.iter().all(|pki| pki.degree == n));
+
+        let ak = AggregateKey::<E>::new(pk.clone(), &params).unwrap();
+        assert_eq!(ak.degree, n);
+
+        // keys from the truncated params cannot be aggregated against the full ones
+        assert!(matches!(
+            AggregateKey::<E>::new(pk.clone(), &big),
+            Err(Error::DegreeMismatch { id: 0, degree: 4, expected: 16 })
+        ));
+
+        // nor mixed with a key computed from the full params
+        pk[2] = sk[2].get_pk(2, &big, n);
+        assert!(matches!(
+            AggregateKey::<E>::new(pk, &params),
+            Err(Error::DegreeMismatch { id: 2, degree: 16, expected: 4 })
+        ));
+
+        let pk: Vec<PublicKey<E>> = (0..n).map(|i| sk[i].get_pk(i, &params, n)).collect();
+        let small = big.truncate(n - 1).unwrap();
+        let pk_small: Vec<PublicKey<E>> = pk.iter().map(|pki| PublicKey { degree: n - 1, ..pki.clone() }).collect();
+        assert!(matches!(
+            AggregateKey::<E>::new(pk_small, &small),
+            Err(Error::CommitteeSizeMismatch { n: 4, degree: 3 })
+        ));
+
+        // a committee smaller than the params, even with keys made for their degree
+        let pk_big: Vec<PublicKey<E>> = (0..n).map(|i| sk[i].get_pk(i, &big, n)).collect();
+        assert!(matches!(
+            AggregateKey::<E>::new(pk_big, &big),
+            Err(Error::CommitteeSizeMismatch { n: 4, degree: 16 })
+        ));
     }
 }
 
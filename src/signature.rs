@@ -0,0 +1,263 @@
+use ark_ec::pairing::Pairing;
+use ark_ff::Field;
+use ark_serialize::*;
+use ark_std::{One, Zero};
+
+use crate::{
+    bls::{HashToG2, SIG_DST},
+    decryption::{aggregate_hints, DecryptionContext, Error, HintAggregate},
+    kzg::UniversalParams,
+    setup::AggregateKey,
+    utils::hash_to_field,
+};
+
+const BATCH_DST: &[u8] = b"SILENT-THRESHOLD-V01-CS01-THRESHOLD-SIGNATURE-BATCH";
+
+/// A BLS signature aggregated from a subset of the committee, together with the
+/// hint openings proving that the subset is large enough for threshold t.
+/// Verifies against the aggregate key alone.
+#[derive(CanonicalSerialize, CanonicalDeserialize, Clone)]
+pub struct ThresholdSignature<E: Pairing> {
+    /// aggregate public key of the signers, weighted by B(omega^i)/n
+    pub apk: E::G1,
+    /// partial signatures aggregated with the same weights as apk
+    pub sigma: E::G2,
+    pub qx: E::G1,
+    pub qz: E::G1,
+    pub qhatx: E::G1,
+    pub b_g2: E::G2,
+    pub q0_g1: E::G1,
+    pub bhat_g1: E::G1,
+}
+
+/// Aggregates partial signatures (SecretKey::sign) on the same message.
+/// Works exactly like agg_dec: insert 0 for parties that did not sign and
+/// set their selector entry to false. The dummy party 0 must be selected, and
+/// at least t slots in all, counting it (see ThresholdSignature::verify).
+/// Fails if partial_signatures or selector is not one entry per slot.
+pub fn sign_aggregate<E: Pairing>(
+    partial_signatures: &[E::G2],
+    t: usize,
+    selector: &[bool],
+    agg_key: &AggregateKey<E>,
+    params: &UniversalParams<E>,
+) -> Result<ThresholdSignature<E>, Error> {
+    let HintAggregate {
+        apk,
+        sigma,
+        qx,
+        qz,
+        qhatx,
+        b_g2,
+        q0_g1,
+        bhat_g1,
+    } = aggregate_hints(partial_signatures, t, selector, agg_key, params)?;
+
+    Ok(ThresholdSignature {
+        apk,
+        sigma,
+        qx,
+        qz,
+        qhatx,
+        b_g2,
+        q0_g1,
+        bhat_g1,
+    })
+}
+
+impl<E: HashToG2> ThresholdSignature<E> {
+    /// Checks that enough parties of agg_key signed msg for threshold t.
+    /// The threshold is enforced exactly as in decryption: with params of degree n
+    /// (KZG10::setup(n)) at least t of the n slots must have signed, and t counts the dummy
+    /// party 0. The dummy party and t - 1 others are enough; the dummy party and t - 2 are not.
+    ///
+    /// The individual checks, batched with Fiat-Shamir powers of r:
+    /// 1. e(ask, B) = e(qz, z) e(qx, h^tau) e(apk, h)      (apk is the B-weighted key sum)
+    /// 2. e(qx, h^tau) = e(qhatx, h)                        (qx has no constant term)
+    /// 3. e(bhat, h) = e(g^{tau^t}, B)                      (deg B <= deg params - t)
+    /// 4. e(q0, h^tau - h) = e(g, B) / e(g, h)              (B(omega^0) = 1)
+    /// 5. e(apk, H(msg)) = e(g, sigma)                      (BLS signature)
+    pub fn verify(
+        &self,
+        msg: &[u8],
+        t: usize,
+        agg_key: &AggregateKey<E>,
+        params: &UniversalParams<E>,
+    ) -> bool {
+        if params.powers_of_h.len() < 2 {
+            return false;
+        }
+        let fixed = [
+            E::G2Prepared::from(agg_key.z_g2),
+            E::G2Prepared::from(params.powers_of_h[1]),
+            E::G2Prepared::from(params.powers_of_h[0]),
+        ];
+        self.verify_prepared(msg, t, agg_key, params, fixed)
+    }
+
+    /// same as verify, reusing the G2 elements the context prepared for the committee
+    pub fn verify_with_context(&self, msg: &[u8], t: usize, ctx: &DecryptionContext<E>) -> bool {
+        let fixed = [ctx.z_g2.clone(), ctx.h_tau.clone(), ctx.h.clone()];
+        self.verify_prepared(msg, t, ctx.agg_key, ctx.params, fixed)
+    }
+
+    /// fixed holds z_g2, h^tau and h, in that order
+    fn verify_prepared(
+        &self,
+        msg: &[u8],
+        t: usize,
+        agg_key: &AggregateKey<E>,
+        params: &UniversalParams<E>,
+        fixed: [E::G2Prepared; 3],
+    ) -> bool {
+        if t >= params.powers_of_g.len() {
+            return false;
+        }
+
+        let g: E::G1 = params.powers_of_g[0].into();
+        let g_t: E::G1 = params.powers_of_g[t].into();
+        let hm = E::hash_to_g2(msg, SIG_DST);
+
+        let r1 = self.batch_challenge(msg, t);
+        let r2 = r1.square();
+        let r3 = r2 * r1;
+        let r4 = r3 * r1;
+        let r5 = r4 * r1;
+
+        let lhs = [
+            agg_key.ask * r1 - g_t * r3 - g * r4,
+            -(self.qz * r1),
+            (self.qx * (r2 - r1)) + (self.q0_g1 * r4),
+            (self.bhat_g1 * r3) + (g * r4) - (self.apk * r1) - (self.qhatx * r2) - (self.q0_g1 * r4),
+            self.apk * r5,
+            -(g * r5),
+        ];
+        let [z_g2, h_tau, h] = fixed;
+        let rhs = [
+            E::G2Prepared::from(self.b_g2),
+            z_g2,
+            h_tau,
+            h,
+            E::G2Prepared::from(hm),
+            E::G2Prepared::from(self.sigma),
+        ];
+
+        E::final_exponentiation(E::multi_miller_loop(lhs, rhs))
+            .unwrap()
+            .is_zero()
+    }
+
+    fn batch_challenge(&self, msg: &[u8], t: usize) -> E::ScalarField {
+        let mut bytes = Vec::new();
+        self.serialize_compressed(&mut bytes).unwrap();
+        msg.serialize_compressed(&mut bytes).unwrap();
+        t.serialize_compressed(&mut bytes).unwrap();
+        let r: E::ScalarField = hash_to_field(BATCH_DST, &bytes);
+        if r.is_zero() {
+            E::ScalarField::one()
+        } else {
+            r
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        kzg::KZG10,
+        setup::{PublicKey, SecretKey},
+    };
+    use ark_poly::univariate::DensePolynomial;
+
+    type E = ark_bls12_381::Bls12_381;
+    type G2 = <E as Pairing>::G2;
+    type UniPoly381 = DensePolynomial<<E as Pairing>::ScalarField>;
+
+    #[test]
+    fn test_threshold_signature() {
+        let mut rng = ark_std::test_rng();
+        let n = 1 << 3;
+        let t: usize = n / 2;
+
+        let params = KZG10::<E, UniPoly381>::setup(n, &mut rng).unwrap();
+
+        let mut sk: Vec<SecretKey<E>> = Vec::new();
+        let mut pk: Vec<PublicKey<E>> = Vec::new();
+
+        // create the dummy party's keys
+        sk.push(SecretKey::<E>::new(&mut rng));
+        sk[0].nullify();
+        pk.push(sk[0].get_pk(0, &params, n));
+
+        for i in 1..n {
+            sk.push(SecretKey::<E>::new(&mut rng));
+            pk.push(sk[i].get_pk(i, &params, n))
+        }
+
+        let agg_key = AggregateKey::<E>::new(pk, &params).unwrap();
+        let msg = b"committee approves manifest";
+
+        // the dummy party and parties 1..=t sign, the rest are absent
+        let mut partials: Vec<G2> = Vec::new();
+        for sk_i in sk.iter().take(t + 1) {
+            partials.push(sk_i.sign(msg));
+        }
+        partials.resize(n, G2::zero());
+
+        let mut selector: Vec<bool> = vec![true; t + 1];
+        selector.resize(n, false);
+
+        let sig = sign_aggregate(&partials, t, &selector, &agg_key, &params).unwrap();
+        assert!(sig.verify(msg, t, &agg_key, &params));
+        assert!(!sig.verify(b"another message", t, &agg_key, &params));
+
+        // t counts the dummy party: these t + 1 slots can sign for t + 1, but not for t + 2,
+        // and a signature made for t does not claim more
+        let sig_above = sign_aggregate(&partials, t + 1, &selector, &agg_key, &params).unwrap();
+        assert!(sig_above.verify(msg, t + 1, &agg_key, &params));
+        assert!(!sig.verify(msg, t + 1, &agg_key, &params));
+        assert!(matches!(
+            sign_aggregate(&partials, t + 2, &selector, &agg_key, &params),
+            Err(Error::ThresholdNotMet { .. })
+        ));
+        assert!(!sig_above.verify(msg, t + 2, &agg_key, &params));
+
+        // exactly t slots, the dummy party and t - 1 others, still meet t
+        let mut fewer = selector.clone();
+        fewer[t] = false;
+        let mut fewer_partials = partials.clone();
+        fewer_partials[t] = G2::zero();
+        let sig_t = sign_aggregate(&fewer_partials, t, &fewer, &agg_key, &params).unwrap();
+        assert!(sig_t.verify(msg, t, &agg_key, &params));
+
+        // t - 1 slots cannot produce a signature for t, and the one they can make fails at t
+        fewer[t - 1] = false;
+        fewer_partials[t - 1] = G2::zero();
+        assert!(matches!(
+            sign_aggregate(&fewer_partials, t, &fewer, &agg_key, &params),
+            Err(Error::ThresholdNotMet { selected, t: 4 }) if selected == t - 1
+        ));
+        let sig_below = sign_aggregate(&fewer_partials, t - 1, &fewer, &agg_key, &params).unwrap();
+        assert!(sig_below.verify(msg, t - 1, &agg_key, &params));
+        assert!(!sig_below.verify(msg, t, &agg_key, &params));
+
+        // the t signers without the dummy party are refused
+        let mut no_dummy = selector.clone();
+        no_dummy[0] = false;
+        assert!(matches!(
+            sign_aggregate(&partials, t, &no_dummy, &agg_key, &params),
+            Err(Error::DummyNotSelected)
+        ));
+
+        let ctx = DecryptionContext::new(&agg_key, &params);
+        assert!(sig.verify_with_context(msg, t, &ctx));
+        assert!(!sig.verify_with_context(b"another message", t, &ctx));
+
+        // a bad partial signature is caught
+        let mut bad = partials.clone();
+        bad[1] = sk[1].sign(b"something else");
+        let sig = sign_aggregate(&bad, t, &selector, &agg_key, &params).unwrap();
+        assert!(!sig.verify(msg, t, &agg_key, &params));
+    }
+}
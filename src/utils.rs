@@ -1,8 +1,11 @@
-use ark_ff::{FftField, Field};
+use ark_ff::field_hashers::{DefaultFieldHasher, HashToField};
+use ark_ff::{batch_inversion, FftField, PrimeField};
 use ark_poly::{
     univariate::DensePolynomial, DenseUVPolynomial, EvaluationDomain, Evaluations, Polynomial,
     Radix2EvaluationDomain,
 };
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+use sha2::{Digest, Sha256};
 
 // 1 at omega^i and 0 elsewhere on domain {omega^i}_{i \in [n]}
 pub fn lagrange_poly<F: FftField>(n: usize, i: usize) -> DensePolynomial<F> {
@@ -19,24 +22,187 @@ pub fn lagrange_poly<F: FftField>(n: usize, i: usize) -> DensePolynomial<F> {
     eval_form.interpolate()
 }
 
-/// interpolates a polynomial when all evaluations except at points[0] are zero
-/// todo: check that multiplication is fast as one polynomial is shorter
-pub fn interp_mostly_zero<F: Field>(eval: F, points: &Vec<F>) -> DensePolynomial<F> {
+/// interpolates a polynomial when all evaluations except at points[0] are zero.
+/// the zeros are multiplied together with a subproduct tree, O(m log^2 m) for m points
+pub fn interp_mostly_zero<F: FftField>(eval: F, points: &[F]) -> DensePolynomial<F> {
     if points.is_empty() {
         // threshold=n
         return DensePolynomial::from_coefficients_vec(vec![F::one()]);
     }
 
-    let mut interp = DensePolynomial::from_coefficients_vec(vec![F::one()]);
-    for &point in &points[1..] {
-        interp = interp.naive_mul(&DensePolynomial::from_coefficients_vec(vec![
-            -point,
-            F::one(),
-        ]));
+    let interp = vanishing_poly(&points[1..]);
+    let scale = interp.evaluate(&points[0]);
+    &interp * (eval / scale)
+}
+
+/// same as interp_mostly_zero with points[0] = omega^0 and the zeros at omega^i for i in absent.
+/// when most of the domain is absent, the zeros are instead recovered from the few present
+/// elements as prod_{absent}(x - omega^i) = (x^n - 1) / prod_{present}(x - omega^i),
+/// dividing pointwise on a coset of the domain. absent must not contain index 0
+pub fn interp_mostly_zero_on_domain<F: FftField>(
+    eval: F,
+    domain: &Radix2EvaluationDomain<F>,
+    absent: &[usize],
+) -> DensePolynomial<F> {
+    assert!(!absent.contains(&0), "interp_mostly_zero_on_domain: index 0 cannot be absent");
+
+    let n = domain.size();
+    let mut is_absent = vec![false; n];
+    for &i in absent {
+        is_absent[i] = true;
     }
+    let num_absent = is_absent.iter().filter(|&&a| a).count();
 
-    let scale = interp.evaluate(&points[0]);
-    interp = &interp * (eval / scale);
+    if 2 * num_absent <= n {
+        let mut points = vec![domain.element(0)];
+        points.extend((1..n).filter(|&i| is_absent[i]).map(|i| domain.element(i)));
+        return interp_mostly_zero(eval, &points);
+    }
+
+    let present: Vec<F> = (0..n).filter(|&i| !is_absent[i]).map(|i| domain.element(i)).collect();
+    let present_poly = vanishing_poly(&present);
+
+    // x^n - 1 is the constant offset^n - 1 on the coset, and present_poly has no roots there
+    let coset = domain.get_coset(F::GENERATOR).unwrap();
+    let z_on_coset = coset.coset_offset_pow_size() - F::one();
+    let mut evals = coset.fft(&present_poly.coeffs);
+    batch_inversion(&mut evals);
+    evals.iter_mut().for_each(|e| *e *= z_on_coset);
+
+    let interp = DensePolynomial::from_coefficients_vec(coset.ifft(&evals));
+    debug_assert_eq!(interp.degree(), num_absent);
+    let scale = interp.evaluate(&domain.element(0));
+    &interp * (eval / scale)
+}
 
-    interp
+/// prod_i (x - points[i]), multiplying halves recursively with FFTs
+pub fn vanishing_poly<F: FftField>(points: &[F]) -> DensePolynomial<F> {
+    // below this size schoolbook multiplication beats the FFT
+    const NAIVE_THRESHOLD: usize = 32;
+
+    if points.len() <= NAIVE_THRESHOLD {
+        let mut poly = DensePolynomial::from_coefficients_vec(vec![F::one()]);
+        for &point in points {
+            poly = poly.naive_mul(&DensePolynomial::from_coefficients_vec(vec![-point, F::one()]));
+        }
+        return poly;
+    }
+
+    let (left, right) = points.split_at(points.len() / 2);
+    &vanishing_poly(left) * &vanishing_poly(right)
+}
+
+/// the polynomial of degree < points.len() taking values[i] at points[i], or None if a point
+/// repeats. the vanishing polynomial Z comes from vanishing_poly and is divided by each
+/// (x - points[i]), scaled by values[i] / Z'(points[i])
+pub fn interpolate<F: FftField>(points: &[F], values: &[F]) -> Option<DensePolynomial<F>> {
+    assert_eq!(points.len(), values.len(), "interpolate: one value per point");
+    let k = points.len();
+    let z = vanishing_poly(points);
+
+    // Z'(points[i]) = prod_{j != i} (points[i] - points[j]), zero exactly when points repeat
+    let z_prime = DensePolynomial::from_coefficients_vec(
+        (1..z.coeffs.len()).map(|i| z.coeffs[i] * F::from(i as u64)).collect(),
+    );
+    let mut scales: Vec<F> = points.iter().map(|p| z_prime.evaluate(p)).collect();
+    if scales.iter().any(|s| s.is_zero()) {
+        return None;
+    }
+    batch_inversion(&mut scales);
+
+    let mut coeffs = vec![F::zero(); k];
+    for ((point, value), scale) in points.iter().zip(values).zip(scales) {
+        // Z / (x - point) by synthetic division
+        let scale = *value * scale;
+        let mut carry = F::zero();
+        for j in (0..k).rev() {
+            carry = z.coeffs[j + 1] + *point * carry;
+            coeffs[j] += carry * scale;
+        }
+    }
+    Some(DensePolynomial::from_coefficients_vec(coeffs))
+}
+
+/// SHA-256 content hash of msg under the domain separation tag dst
+pub fn fingerprint(dst: &[u8], msg: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update((dst.len() as u64).to_le_bytes());
+    hasher.update(dst);
+    hasher.update(msg);
+    hasher.finalize().into()
+}
+
+/// hashes msg to a single field element under the domain separation tag dst
+/// used to derive Fiat-Shamir challenges
+pub fn hash_to_field<F: PrimeField>(dst: &[u8], msg: &[u8]) -> F {
+    let hasher = <DefaultFieldHasher<Sha256> as HashToField<F>>::new(dst);
+    hasher.hash_to_field(msg, 1)[0]
+}
+
+/// reads a value in the canonical uncompressed encoding, rejecting trailing bytes
+pub(crate) fn checked<T: CanonicalDeserialize>(bytes: &[u8]) -> Result<T, SerializationError> {
+    let mut reader = bytes;
+    let value = T::deserialize_uncompressed(&mut reader)?;
+    if !reader.is_empty() {
+        return Err(SerializationError::InvalidData);
+    }
+    Ok(value)
+}
+
+pub(crate) fn to_bytes<T: CanonicalSerialize>(value: &T) -> Vec<u8> {
+    let mut data = Vec::with_capacity(value.uncompressed_size());
+    value.serialize_uncompressed(&mut data).unwrap();
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_std::{UniformRand, Zero};
+
+    type F = ark_bls12_381::Fr;
+
+    #[test]
+    fn test_interp_mostly_zero() {
+        let mut rng = ark_std::test_rng();
+        let n = 1 << 8;
+        let domain = Radix2EvaluationDomain::<F>::new(n).unwrap();
+        let eval = F::rand(&mut rng);
+
+        // a few absent (subproduct tree) and most absent (division of x^n - 1)
+        for num_present in [n - 3, n - 100, n / 2, 40, 3, 1] {
+            let absent: Vec<usize> = (num_present..n).collect();
+            let b = interp_mostly_zero_on_domain(eval, &domain, &absent);
+
+            assert_eq!(b.degree(), absent.len());
+            assert_eq!(b.evaluate(&domain.element(0)), eval);
+            for &i in &absent {
+                assert!(b.evaluate(&domain.element(i)).is_zero());
+            }
+
+            let mut points = vec![domain.element(0)];
+            points.extend(absent.iter().map(|&i| domain.element(i)));
+            assert_eq!(b, interp_mostly_zero(eval, &points));
+        }
+
+        // everyone present
+        assert_eq!(interp_mostly_zero_on_domain(eval, &domain, &[]).coeffs, vec![eval]);
+
+        // the tree agrees with multiplying the factors one at a time
+        let points: Vec<F> = (0..100).map(|_| F::rand(&mut rng)).collect();
+        let mut naive = DensePolynomial::from_coefficients_vec(vec![F::from(1u64)]);
+        for &p in &points {
+            naive = naive.naive_mul(&DensePolynomial::from_coefficients_vec(vec![-p, F::from(1u64)]));
+        }
+        assert_eq!(vanishing_poly(&points), naive);
+
+        // interpolation through the same vanishing polynomial, and repeated points
+        let values: Vec<F> = (0..100).map(|_| F::rand(&mut rng)).collect();
+        let interp = interpolate(&points, &values).unwrap();
+        assert!(interp.degree() < points.len());
+        for (p, v) in points.iter().zip(values.iter()) {
+            assert_eq!(interp.evaluate(p), *v);
+        }
+        assert!(interpolate(&[points[0], points[1], points[0]], &values[..3]).is_none());
+    }
 }
@@ -0,0 +1,166 @@
+//! Handle-based wasm API.
+//!
+//! The objects are parsed once and stay in wasm memory; JS only holds handles to them.
+//! Call free() on a handle to release it early, otherwise it is released when garbage collected.
+//! They are read from and written to Uint8Arrays in the canonical uncompressed encoding.
+
+use std::rc::Rc;
+
+use ark_ec::pairing::Pairing;
+use ark_ff::Zero;
+use rand_core::OsRng;
+use wasm_bindgen::prelude::*;
+
+use crate::decryption::agg_dec_checked;
+use crate::encryption::{encrypt_with_label, Ciphertext};
+use crate::kzg::{UniversalParams, KZG10};
+use crate::setup::AggregateKey;
+use super::wrappers::{checked, parse_aggregate_key, to_bytes, ProjectiveG2Wrapper, SecretKeyWrapper};
+use super::{E, UniPoly381};
+
+#[wasm_bindgen]
+pub struct Params {
+    inner: Rc<UniversalParams<E>>,
+}
+
+#[wasm_bindgen]
+pub struct AggregateKeyHandle {
+    inner: AggregateKey<E>,
+    params: Rc<UniversalParams<E>>,
+}
+
+#[wasm_bindgen(js_name = Ciphertext)]
+pub struct CiphertextHandle {
+    inner: Ciphertext<E>,
+}
+
+#[wasm_bindgen]
+impl Params {
+    /// runs the setup for a committee of size parties, like setup_wasm
+    #[wasm_bindgen(constructor)]
+    pub fn new(size: usize) -> Result<Params, JsError> {
+        let params = KZG10::<E, UniPoly381>::setup(size, &mut OsRng)
+            .map_err(|e| JsError::new(&format!("{:?}", e)))?;
+        Ok(Params {
+            inner: Rc::new(params),
+        })
+    }
+
+    /// parses and checks params returned by setup_wasm or to_bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Params, JsError> {
+        Ok(Params {
+            inner: Rc::new(checked::<UniversalParams<E>>(bytes)?),
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        to_bytes(&*self.inner)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn degree(&self) -> usize {
+        self.inner.degree()
+    }
+
+    /// encrypts to agg_key with threshold t. agg_key must have been read with these params
+    pub fn encrypt(&self, agg_key: &AggregateKeyHandle, t: usize) -> Result<CiphertextHandle, JsError> {
+        if agg_key.params.fingerprint() != self.inner.fingerprint() {
+            return Err(JsError::new("the aggregate key was built for different params"));
+        }
+        if t >= self.inner.powers_of_g.len() {
+            return Err(JsError::new("threshold exceeds the degree of the params"));
+        }
+        Ok(CiphertextHandle {
+            inner: encrypt_with_label::<E, _>(&agg_key.inner, t, &self.inner, &[], &mut OsRng),
+        })
+    }
+}
+
+#[wasm_bindgen]
+impl AggregateKeyHandle {
+    /// parses the agg_key returned by generate_keys_wasm, sharing params with the handle
+    pub fn from_bytes(bytes: &[u8], params: &Params) -> Result<AggregateKeyHandle, JsError> {
+        Ok(AggregateKeyHandle {
+            inner: parse_aggregate_key(bytes, &params.inner)?,
+            params: params.inner.clone(),
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        to_bytes(&self.inner)
+    }
+
+    /// number of parties, including the dummy party 0
+    #[wasm_bindgen(getter)]
+    pub fn size(&self) -> usize {
+        self.inner.pk.len()
+    }
+
+    /// stable hash of the committee, as recorded in ciphertexts and keystores
+    #[wasm_bindgen(getter)]
+    pub fn fingerprint(&self) -> Vec<u8> {
+        self.inner.fingerprint().to_vec()
+    }
+
+    /// recovers the encapsulated key of ct. partials[i] is party i's partial decryption
+    /// as a Uint8Array, or null/undefined if it did not respond; missing trailing entries
+    /// count as absent
+    pub fn combine(&self, partials: Vec<JsValue>, ct: &CiphertextHandle) -> Result<Vec<u8>, JsError> {
+        let n = self.inner.pk.len();
+        if partials.len() > n {
+            return Err(JsError::new("more partial decryptions than parties"));
+        }
+
+        let mut selector = vec![false; n];
+        let mut partial_decryptions = vec![<E as Pairing>::G2::zero(); n];
+        for (i, partial) in partials.iter().enumerate() {
+            if partial.is_null() || partial.is_undefined() {
+                continue;
+            }
+            let partial = ProjectiveG2Wrapper::from_js_bytes::<E>(partial)?;
+            partial_decryptions[i] = partial.to_g2::<E>()?;
+            selector[i] = true;
+        }
+
+        let dec_key = agg_dec_checked(&partial_decryptions, &ct.inner, &selector, &self.inner, &self.params)
+            .map_err(|e| JsError::new(&format!("{:?}", e)))?;
+        Ok(to_bytes(&dec_key))
+    }
+}
+
+#[wasm_bindgen(js_class = Ciphertext)]
+impl CiphertextHandle {
+    /// parses a ciphertext returned by encrypt_wasm or to_bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<CiphertextHandle, JsError> {
+        Ok(CiphertextHandle {
+            inner: checked::<Ciphertext<E>>(bytes)?,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        to_bytes(&self.inner)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn threshold(&self) -> usize {
+        self.inner.t
+    }
+}
+
+#[wasm_bindgen]
+impl SecretKeyWrapper {
+    /// partial decryption of a ciphertext handle, in the format combine expects.
+    /// the ciphertext must verify against agg_key and label (empty for Params.encrypt)
+    pub fn partial_decryption(
+        &self,
+        ct: &CiphertextHandle,
+        agg_key: &AggregateKeyHandle,
+        label: Option<Vec<u8>>,
+    ) -> Result<Vec<u8>, JsError> {
+        let result = self
+            .to_secret_key()
+            .partial_decryption(&ct.inner, &label.unwrap_or_default(), &agg_key.inner, &agg_key.params)
+            .map_err(|e| JsError::new(&format!("{:?}", e)))?;
+        Ok(ProjectiveG2Wrapper::from_g2::<E>(result).to_bytes())
+    }
+}
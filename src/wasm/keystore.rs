@@ -0,0 +1,94 @@
+//! Passphrase-protected secret keys for browsers, e.g. to persist them in local storage.
+//!
+//! scrypt is deliberately slow (about a second at the default cost), so call these from a
+//! Worker rather than the page itself.
+
+use rand_core::OsRng;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::keystore::{self, KeyMetadata};
+use crate::kzg::UniversalParams;
+use crate::setup::SecretKey;
+use super::wrappers::{checked, to_bytes, SecretKeyWrapper};
+use super::E;
+
+#[derive(Serialize)]
+struct KeystoreInfo {
+    curve: &'static str,
+    slot: usize,
+    #[serde(with = "serde_bytes_option")]
+    committee: Option<Vec<u8>>,
+}
+
+// Uint8Array instead of a plain array of numbers
+mod serde_bytes_option {
+    use serde::Serializer;
+
+    pub fn serialize<S: Serializer>(value: &Option<Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(bytes) => serializer.serialize_bytes(bytes),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+fn js_error(err: keystore::Error) -> JsError {
+    JsError::new(&format!("{:?}", err))
+}
+
+#[wasm_bindgen]
+impl SecretKeyWrapper {
+    /// a fresh secret key drawn from crypto.getRandomValues, for a party that keeps its own key
+    pub fn generate() -> SecretKeyWrapper {
+        SecretKey::<E>::new(&mut OsRng).into()
+    }
+
+    /// the public key bytes of party slot, in 1..params.degree, to pass on for aggregation
+    pub fn public_key(&self, params: &[u8], slot: usize) -> Result<Vec<u8>, JsError> {
+        let params: UniversalParams<E> = checked(params)?;
+        let n = super::committee_size(&params)?;
+        if slot == 0 || slot >= n {
+            return Err(JsError::new(&format!("slot must be in 1..{}, slot 0 is the dummy party", n)));
+        }
+        Ok(to_bytes(&self.to_secret_key().get_pk(slot, &params, n)))
+    }
+
+    /// encrypts the key under passphrase into a JSON keystore for party slot. committee is
+    /// AggregateKeyHandle.fingerprint, if the committee has been formed
+    pub fn export_encrypted(
+        &self,
+        passphrase: &str,
+        slot: usize,
+        committee: Option<Vec<u8>>,
+    ) -> Result<String, JsError> {
+        let committee = match committee {
+            Some(bytes) => Some(
+                <[u8; 32]>::try_from(bytes.as_slice())
+                    .map_err(|_| JsError::new("committee fingerprint must be 32 bytes"))?,
+            ),
+            None => None,
+        };
+        let metadata = KeyMetadata { slot, committee };
+        Ok(self.to_secret_key().export_encrypted(passphrase, &metadata, &mut OsRng))
+    }
+
+    /// decrypts a keystore written by export_encrypted; keystore_info reads its slot and committee
+    pub fn import_encrypted(keystore: &str, passphrase: &str) -> Result<SecretKeyWrapper, JsError> {
+        let (sk, _) = SecretKey::<E>::import_encrypted(keystore, passphrase).map_err(js_error)?;
+        Ok(sk.into())
+    }
+}
+
+/// returns { curve, slot, committee: Uint8Array | undefined } of a keystore, without the passphrase.
+/// this is not authenticated, anyone can edit it; only a successful import_encrypted vouches for it
+#[wasm_bindgen]
+pub fn keystore_info(keystore: &str) -> Result<JsValue, JsError> {
+    let metadata = keystore::read_metadata::<E>(keystore).map_err(js_error)?;
+    let info = KeystoreInfo {
+        curve: <E as keystore::CurveName>::CURVE,
+        slot: metadata.slot,
+        committee: metadata.committee.map(|c| c.to_vec()),
+    };
+    Ok(serde_wasm_bindgen::to_value(&info)?)
+}
@@ -0,0 +1,113 @@
+//! Forwards the crate's tracing spans and events to the JS console.
+//!
+//! Nothing is logged until init_logging is called. Events go to console.debug/info/warn/error
+//! by level, prefixed with the spans they happened in; every span is reported when it closes,
+//! with its fields and the wall-clock time it was open as elapsed_ms.
+
+use std::fmt::{self, Write};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Record};
+use tracing::{Event, Id, Level, Subscriber};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = performance)]
+    fn now() -> f64;
+
+    #[wasm_bindgen(js_namespace = console, js_name = debug)]
+    fn console_debug(s: &str);
+    #[wasm_bindgen(js_namespace = console, js_name = info)]
+    fn console_info(s: &str);
+    #[wasm_bindgen(js_namespace = console, js_name = warn)]
+    fn console_warn(s: &str);
+    #[wasm_bindgen(js_namespace = console, js_name = error)]
+    fn console_error(s: &str);
+}
+
+/// installs the console subscriber for spans and events up to level
+/// ("error", "warn", "info", "debug" or "trace", default "info").
+/// Builds with the no-logging feature have nothing to report.
+#[wasm_bindgen]
+pub fn init_logging(level: Option<String>) -> Result<(), JsError> {
+    let level: Level = level.as_deref().unwrap_or("info").parse()?;
+    let subscriber = tracing_subscriber::registry().with(ConsoleLayer.with_filter(LevelFilter::from_level(level)));
+    tracing::subscriber::set_global_default(subscriber)?;
+    Ok(())
+}
+
+struct ConsoleLayer;
+
+/// what ConsoleLayer keeps in a span's extensions until it closes
+struct SpanTiming {
+    start: f64,
+    fields: String,
+}
+
+/// formats fields as " name=value", with the message of an event first and unnamed
+struct FieldWriter<'a>(&'a mut String);
+
+impl Visit for FieldWriter<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.0, " {:?}", value);
+        } else {
+            let _ = write!(self.0, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+fn console(level: &Level, line: &str) {
+    match *level {
+        Level::ERROR => console_error(line),
+        Level::WARN => console_warn(line),
+        Level::INFO => console_info(line),
+        _ => console_debug(line),
+    }
+}
+
+impl<S> Layer<S> for ConsoleLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut fields = String::new();
+        attrs.record(&mut FieldWriter(&mut fields));
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SpanTiming { start: now(), fields });
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(timing) = span.extensions_mut().get_mut::<SpanTiming>() {
+                values.record(&mut FieldWriter(&mut timing.fields));
+            }
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut line = String::new();
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                line.push_str(span.name());
+                line.push(':');
+            }
+        }
+        event.record(&mut FieldWriter(&mut line));
+        console(event.metadata().level(), line.trim_start());
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else { return };
+        let extensions = span.extensions();
+        let Some(timing) = extensions.get::<SpanTiming>() else { return };
+        let line = format!("{}{} elapsed_ms={:.1}", span.name(), timing.fields, now() - timing.start);
+        console(span.metadata().level(), &line);
+    }
+}
@@ -0,0 +1,152 @@
+//! JS bindings, compiled with the wasm feature.
+//!
+//! Everything that crosses the wasm boundary lives here, so that the rest of the crate
+//! builds natively without wasm-bindgen or any of the JS glue.
+
+pub mod handles;
+pub mod keystore;
+#[cfg(target_arch = "wasm32")]
+pub mod logging;
+pub mod tasks;
+pub mod wrappers;
+
+use ark_poly::univariate::DensePolynomial;
+use js_sys::Uint8Array;
+use rand_core::OsRng;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsValue;
+use ark_bls12_381::Bls12_381;
+use ark_ec::pairing::{Pairing};
+use serde_wasm_bindgen::from_value;
+
+use crate::setup::{AggregateKey, PublicKey, SecretKey};
+use crate::encryption::{Ciphertext, encrypt_with_label};
+use crate::decryption::agg_dec_checked;
+use crate::kzg::{UniversalParams, KZG10};
+use wrappers::*;
+
+type E = Bls12_381;
+type UniPoly381 = DensePolynomial<<E as Pairing>::ScalarField>;
+
+// starts the rayon pool on Web Workers, exported to JS as initThreadPool(n).
+// only present in the shared-memory build with the wasm-threads feature, see the README
+#[cfg(all(target_arch = "wasm32", feature = "wasm-threads"))]
+pub use wasm_bindgen_rayon::init_thread_pool;
+
+#[wasm_bindgen(start)]
+pub fn main() {
+    console_error_panic_hook::set_once();
+}
+
+/// params for a committee of size parties, size a power of two
+#[wasm_bindgen]
+pub fn setup_wasm(size: usize) -> Result<Vec<u8>, JsError> {
+    let params = KZG10::<E, UniPoly381>::setup(size, &mut OsRng)
+        .map_err(|e| JsError::new(&format!("{:?}", e)))?;
+
+    Ok(to_bytes(&params))
+}
+
+/// the committee size the params are for, which must be their degree
+pub(crate) fn committee_size(params: &UniversalParams<E>) -> Result<usize, JsError> {
+    let n = params.degree();
+    if !n.is_power_of_two() || n < 2 {
+        return Err(JsError::new("the degree of the params must be a power of two, at least 2"));
+    }
+    Ok(n)
+}
+
+/// returns { sk: Uint8Array[], agg_key: Uint8Array } for a committee of params.degree parties.
+/// sk[0] is the dummy party's key, which is 1
+#[wasm_bindgen]
+pub fn generate_keys_wasm(params: &[u8]) -> Result<JsValue, JsError> {
+    let _span = tracing::info_span!("generate_keys_wasm").entered();
+    let params: UniversalParams<E> = checked(params)?;
+    let n = committee_size(&params)?;
+    tracing::debug!(n, "parsed params");
+
+    let sk = js_sys::Array::new();
+    let mut pk: Vec<PublicKey<E>> = Vec::new();
+
+    for i in 0..n {
+        let mut sk_i = SecretKey::<E>::new(&mut OsRng);
+        // slot 0 is the dummy party with sk = 1, as in the CLI and the C ABI
+        if i == 0 {
+            sk_i.nullify();
+        }
+        pk.push(sk_i.get_pk(i, &params, n));
+        sk.push(&Uint8Array::from(&sk_i.export_secret_bytes()[..]));
+    }
+
+    let agg_key = AggregateKey::<E>::new(pk, &params)
+        .map_err(|e| JsError::new(&format!("{:?}", e)))?;
+    let agg_key = to_bytes(&agg_key);
+
+    let result = js_sys::Object::new();
+    js_sys::Reflect::set(&result, &JsValue::from_str("sk"), &sk).unwrap();
+    js_sys::Reflect::set(&result, &JsValue::from_str("agg_key"), &Uint8Array::from(&agg_key[..])).unwrap();
+
+    Ok(result.into())
+}
+
+#[wasm_bindgen]
+pub fn encrypt_wasm(agg_key: &[u8], t: usize, params: &[u8]) -> Result<Vec<u8>, JsError> {
+    let params: UniversalParams<E> = checked(params)?;
+    let agg_key = parse_aggregate_key(agg_key, &params)?;
+    if t >= params.powers_of_g.len() {
+        return Err(JsError::new("threshold exceeds the degree of the params"));
+    }
+    let ct = encrypt_with_label::<E, _>(&agg_key, t, &params, &[], &mut OsRng);
+    Ok(to_bytes(&ct))
+}
+
+/// reads an array of partial decryptions, each a Uint8Array holding a G2 point
+pub fn convert_partial_decryptions(js_array: JsValue) -> Result<Vec<Vec<u8>>, JsValue> {
+    let array = js_sys::Array::from(&js_array);
+
+    let mut result: Vec<Vec<u8>> = Vec::new();
+
+    for i in 0..array.length() {
+        let element = array.get(i);
+        let uint8_array = element
+            .dyn_ref::<Uint8Array>()
+            .ok_or_else(|| JsValue::from_str("partial decryption must be a Uint8Array"))?;
+        result.push(uint8_array.to_vec());
+    }
+
+    Ok(result)
+}
+
+#[wasm_bindgen]
+pub fn decrypt_wasm(partial_decryptions: JsValue, ct: &[u8], selector: JsValue, agg_key: &[u8], params: &[u8]) -> Result<Vec<u8>, JsError> {
+    let _span = tracing::info_span!("decrypt_wasm").entered();
+
+    let partial_decryptions: Vec<Vec<u8>> = convert_partial_decryptions(partial_decryptions)
+        .map_err(|_| JsError::new("partial decryptions must be an array of Uint8Array"))?;
+
+    let ct: Ciphertext<E> = checked(ct)?;
+    tracing::debug!(t = ct.t, "parsed ciphertext");
+
+    let selector: Vec<bool> = from_value(selector)?;
+    if selector.len() != partial_decryptions.len() {
+        return Err(JsError::new("expected one partial decryption per party in the selector"));
+    }
+
+    let params: UniversalParams<E> = checked(params)?;
+    tracing::debug!(degree = params.degree(), "parsed params");
+
+    let agg_key = parse_aggregate_key(agg_key, &params)?;
+    tracing::debug!(n = agg_key.pk.len(), "parsed aggregate key");
+
+    let partial_decryptions = partial_decryptions.iter()
+        .map(|g| checked(g))
+        .collect::<Result<Vec<<E as Pairing>::G2>, _>>()?;
+
+    let dec_key = agg_dec_checked(&partial_decryptions, &ct, &selector, &agg_key, &params)
+        .map_err(|e| JsError::new(&format!("{:?}", e)))?;
+
+    // Drop the partial_decryptions variable after its last use
+    drop(partial_decryptions);
+
+    Ok(to_bytes(&dec_key))
+}
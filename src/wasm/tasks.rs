@@ -0,0 +1,173 @@
+//! Promise-returning versions of the long-running exports.
+//!
+//! They do the same work as setup_wasm, generate_keys_wasm and decrypt_wasm, but in chunks,
+//! yielding to the event loop in between so that the page stays responsive. Each takes an
+//! optional progress callback, called as progress(phase, fraction) with the fraction of the
+//! phase done so far, and an optional AbortSignal; an aborted task rejects with the signal's
+//! reason at the next chunk boundary.
+
+use ark_ec::pairing::Pairing;
+use js_sys::{Array, Function, Object, Promise, Reflect, Uint8Array};
+use rand_core::OsRng;
+use serde_wasm_bindgen::from_value;
+use tracing::Instrument;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::AbortSignal;
+
+use crate::decryption::DecryptionContext;
+use crate::encryption::Ciphertext;
+use crate::kzg::{SetupSteps, UniversalParams};
+use crate::setup::{AggregateKey, PublicKey, SecretKey};
+use super::wrappers::{checked, parse_aggregate_key, to_bytes};
+use super::E;
+
+/// powers computed between two yields of setup_async
+const SETUP_CHUNK: usize = 32;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = setTimeout)]
+    fn set_timeout(handler: &Function, timeout: i32);
+}
+
+/// the progress callback and abort signal of a running task
+struct Task {
+    progress: Option<Function>,
+    signal: Option<AbortSignal>,
+}
+
+impl Task {
+    fn new(progress: Option<Function>, signal: Option<AbortSignal>) -> Result<Self, JsValue> {
+        let task = Task { progress, signal };
+        task.check_aborted()?;
+        Ok(task)
+    }
+
+    /// reports progress, then yields to the event loop and stops if the task was aborted meanwhile
+    async fn checkpoint(&self, phase: &str, fraction: f64) -> Result<(), JsValue> {
+        if let Some(progress) = &self.progress {
+            progress.call2(&JsValue::NULL, &JsValue::from_str(phase), &JsValue::from_f64(fraction))?;
+        }
+        // a zero timeout, unlike a resolved promise, lets rendering and input events through
+        JsFuture::from(Promise::new(&mut |resolve, _| set_timeout(&resolve, 0))).await?;
+        self.check_aborted()
+    }
+
+    fn check_aborted(&self) -> Result<(), JsValue> {
+        match &self.signal {
+            Some(signal) if signal.aborted() => Err(signal.reason()),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn parse<T: ark_serialize::CanonicalDeserialize>(bytes: &[u8]) -> Result<T, JsValue> {
+    checked(bytes).map_err(|e| JsError::from(e).into())
+}
+
+/// setup_wasm, reporting the "setup" phase
+#[wasm_bindgen]
+pub async fn setup_async(
+    size: usize,
+    progress: Option<Function>,
+    signal: Option<AbortSignal>,
+) -> Result<Uint8Array, JsValue> {
+    let task = Task::new(progress, signal)?;
+    // the same span as KZG10::setup, kept open across the yields
+    let params = async {
+        let mut steps = SetupSteps::<E>::new(size, &mut OsRng).map_err(|e| JsError::new(&format!("{:?}", e)))?;
+        task.checkpoint("setup", 0.0).await?;
+        while !steps.step(SETUP_CHUNK) {
+            task.checkpoint("setup", steps.progress()).await?;
+        }
+        Ok::<_, JsValue>(steps.finish())
+    }
+    .instrument(tracing::info_span!("setup", max_degree = size))
+    .await?;
+    task.checkpoint("setup", 1.0).await?;
+
+    Ok(Uint8Array::from(&to_bytes(&params)[..]))
+}
+
+/// generate_keys_wasm, reporting the "parse", "keygen" (one chunk per party) and "aggregate" phases
+#[wasm_bindgen]
+pub async fn generate_keys_async(
+    params: Vec<u8>,
+    progress: Option<Function>,
+    signal: Option<AbortSignal>,
+) -> Result<JsValue, JsValue> {
+    let task = Task::new(progress, signal)?;
+
+    task.checkpoint("parse", 0.0).await?;
+    let params: UniversalParams<E> = parse(&params)?;
+    let n = super::committee_size(&params)?;
+    let sk = Array::new();
+    let mut pk: Vec<PublicKey<E>> = Vec::new();
+    for i in 0..n {
+        task.checkpoint("keygen", i as f64 / n as f64).await?;
+        let mut sk_i = SecretKey::<E>::new(&mut OsRng);
+        // slot 0 is the dummy party with sk = 1, as in generate_keys_wasm
+        if i == 0 {
+            sk_i.nullify();
+        }
+        pk.push(sk_i.get_pk(i, &params, n));
+        sk.push(&Uint8Array::from(&sk_i.export_secret_bytes()[..]));
+    }
+
+    task.checkpoint("aggregate", 0.0).await?;
+    let agg_key = AggregateKey::<E>::new(pk, &params).map_err(|e| JsError::new(&format!("{:?}", e)))?;
+    task.checkpoint("aggregate", 1.0).await?;
+
+    let result = Object::new();
+    Reflect::set(&result, &JsValue::from_str("sk"), &sk)?;
+    Reflect::set(&result, &JsValue::from_str("agg_key"), &Uint8Array::from(&to_bytes(&agg_key)[..]))?;
+    Ok(result.into())
+}
+
+/// decrypt_wasm, reporting the "parse", "prepare" and "combine" phases
+#[wasm_bindgen]
+pub async fn decrypt_async(
+    partial_decryptions: Vec<Uint8Array>,
+    ct: Vec<u8>,
+    selector: JsValue,
+    agg_key: Vec<u8>,
+    params: Vec<u8>,
+    progress: Option<Function>,
+    signal: Option<AbortSignal>,
+) -> Result<Uint8Array, JsValue> {
+    let task = Task::new(progress, signal)?;
+
+    let selector: Vec<bool> = from_value(selector)?;
+    if selector.len() != partial_decryptions.len() {
+        return Err(JsError::new("expected one partial decryption per party in the selector").into());
+    }
+
+    // the params and the aggregate key carry most of the points to check
+    task.checkpoint("parse", 0.0).await?;
+    let params: UniversalParams<E> = parse(&params)?;
+    task.checkpoint("parse", 0.4).await?;
+    let agg_key = parse_aggregate_key(&agg_key, &params)?;
+    task.checkpoint("parse", 0.8).await?;
+    let ct: Ciphertext<E> = parse(&ct)?;
+    let partial_decryptions = partial_decryptions
+        .iter()
+        .map(|partial| parse(&partial.to_vec()))
+        .collect::<Result<Vec<<E as Pairing>::G2>, _>>()?;
+
+    task.checkpoint("prepare", 0.0).await?;
+    let context = DecryptionContext::new(&agg_key, &params);
+    let ct = context.prepare(&ct).map_err(|e| JsError::new(&format!("{:?}", e)))?;
+
+    // the steps of DecryptionContext::agg_dec_checked, roughly equal in cost
+    let error = |e| JsError::new(&format!("{:?}", e));
+    task.checkpoint("combine", 0.0).await?;
+    let hints = context.aggregate(&partial_decryptions, &ct, &selector).map_err(error)?;
+    task.checkpoint("combine", 0.4).await?;
+    context.check_aggregate(&hints, &ct).map_err(error)?;
+    task.checkpoint("combine", 0.7).await?;
+    let dec_key = context.decapsulate(&hints, &ct);
+    task.checkpoint("combine", 1.0).await?;
+
+    Ok(Uint8Array::from(&to_bytes(&dec_key)[..]))
+}
@@ -1,11 +1,13 @@
 use wasm_bindgen::prelude::*;
 use ark_ec::pairing::{Pairing, PairingOutput};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+use js_sys::Uint8Array;
 use serde::de::{self, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_wasm_bindgen::{from_value, to_value};
 use std::fmt::{self};
 use std::marker::PhantomData;
+use zeroize::Zeroizing;
 
 use crate::setup::{AggregateKey, SecretKey, PublicKey};
 use crate::encryption::Ciphertext;
@@ -13,6 +15,16 @@ use crate::kzg::UniversalParams;
 
 type E = ark_bls12_381::Bls12_381;
 
+// All wrappers hold their value in the canonical uncompressed encoding, which is also
+// what from_bytes/to_bytes read and write. from_bytes parses the bytes once to reject
+// malformed input up front, including trailing garbage.
+pub(crate) use crate::utils::{checked, to_bytes};
+
+/// reads an aggregate key, aggregating its public keys again over params
+pub(crate) fn parse_aggregate_key(bytes: &[u8], params: &UniversalParams<E>) -> Result<AggregateKey<E>, JsError> {
+    AggregateKey::from_bytes(bytes, params).map_err(|e| JsError::new(&format!("{:?}", e)))
+}
+
 // Wrapper for PublicKey
 #[wasm_bindgen]
 #[derive(Serialize, Deserialize, Clone)]
@@ -28,72 +40,85 @@ impl From<PublicKey<E>> for PublicKeyWrapper {
     }
 }
 
-impl From<PublicKeyWrapper> for PublicKey<E> {
-    fn from(val: PublicKeyWrapper) -> Self {
-        PublicKey::deserialize_uncompressed(&val.data[..]).unwrap()
+impl TryFrom<PublicKeyWrapper> for PublicKey<E> {
+    type Error = SerializationError;
+
+    fn try_from(val: PublicKeyWrapper) -> Result<Self, Self::Error> {
+        checked(&val.data)
     }
 }
 
 #[wasm_bindgen]
 impl PublicKeyWrapper {
+    pub fn from_bytes(bytes: &[u8]) -> Result<PublicKeyWrapper, JsError> {
+        checked::<PublicKey<E>>(bytes)?;
+        Ok(Self { data: bytes.to_vec() })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.data.clone()
+    }
+
+    // read only: from_bytes is the one way in, so the bytes are always checked
     #[wasm_bindgen(getter)]
     pub fn data(&self) -> Box<[u8]> {
         self.data.clone().into_boxed_slice()
     }
-
-    #[wasm_bindgen(setter)]
-    pub fn set_data(&mut self, data: Box<[u8]>) {
-        self.data = data.into_vec();
-    }
 }
 
-// Wrapper for SecretKey
+// Wrapper for SecretKey, holding the exported secret bytes, which are wiped on drop
 #[wasm_bindgen]
-#[derive(Serialize, Deserialize, Clone)]
 pub struct SecretKeyWrapper {
-    data: Vec<u8>,
+    data: Zeroizing<Vec<u8>>,
+}
+
+impl fmt::Debug for SecretKeyWrapper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretKeyWrapper(<redacted>)")
+    }
 }
 
 impl From<SecretKey<E>> for SecretKeyWrapper {
     fn from(sk: SecretKey<E>) -> Self {
-        let mut data = Vec::new();
-        sk.serialize_uncompressed(&mut data).unwrap();
-        Self { data }
+        Self { data: sk.export_secret_bytes() }
     }
 }
 
-#[wasm_bindgen]
 impl SecretKeyWrapper {
-    #[wasm_bindgen(getter)]
-    pub fn data(&self) -> Box<[u8]> {
-        self.data.clone().into_boxed_slice()
-    }
-
-    #[wasm_bindgen(setter)]
-    pub fn set_data(&mut self, data: Box<[u8]>) {
-        self.data = data.into_vec();
-    }
-
-    #[wasm_bindgen]
     pub fn to_secret_key(&self) -> SecretKey<E> {
-        SecretKey::deserialize_uncompressed(&self.data[..]).unwrap()
+        SecretKey::from_secret_bytes(&self.data).unwrap()
     }
+}
 
-    #[wasm_bindgen]
-    pub fn from_js_value(js_value: JsValue) -> SecretKeyWrapper {
-        from_value(js_value).unwrap()
+#[wasm_bindgen]
+impl SecretKeyWrapper {
+    pub fn from_bytes(bytes: &[u8]) -> Result<SecretKeyWrapper, JsError> {
+        SecretKey::<E>::from_secret_bytes(bytes)?;
+        Ok(Self { data: Zeroizing::new(bytes.to_vec()) })
     }
 
-    pub fn to_js_value(&self) -> JsValue {
-        to_value(&self).unwrap()
+    /// the secret scalar bytes that from_bytes reads. the copy handed to JS is not wiped
+    pub fn export_secret_bytes(&self) -> Vec<u8> {
+        self.data.to_vec()
     }
 
-    pub fn partial_decryption_js(&self, ct: JsValue) -> JsValue {
-        let sk = self.to_secret_key();
-        let ct: CiphertextWrapper = from_value(ct).unwrap();
-        let ct = ct.to_ciphertext();
-        let result = sk.partial_decryption(&ct);
-        to_value(&ProjectiveG2Wrapper::from_g2::<E>(result)).unwrap()
+    /// partial decryption of a ciphertext in the canonical encoding, as G2 point bytes.
+    /// the ciphertext must verify against agg_key and label (empty for encrypt_wasm)
+    pub fn partial_decryption_js(
+        &self,
+        ct: &[u8],
+        agg_key: &[u8],
+        params: &[u8],
+        label: Option<Vec<u8>>,
+    ) -> Result<Vec<u8>, JsError> {
+        let ct: Ciphertext<E> = checked(ct)?;
+        let params: UniversalParams<E> = checked(params)?;
+        let agg_key = parse_aggregate_key(agg_key, &params)?;
+        let result = self
+            .to_secret_key()
+            .partial_decryption(&ct, &label.unwrap_or_default(), &agg_key, &params)
+            .map_err(|e| JsError::new(&format!("{:?}", e)))?;
+        Ok(to_bytes(&result))
     }
 }
 
@@ -111,19 +136,27 @@ impl From<UniversalParams<E>> for UniversalParamsWrapper {
     }
 }
 
-impl From<UniversalParamsWrapper> for UniversalParams<E> {
-    fn from(val: UniversalParamsWrapper) -> Self {
-        UniversalParams::deserialize_uncompressed(&val.data[..]).unwrap()
+impl TryFrom<UniversalParamsWrapper> for UniversalParams<E> {
+    type Error = SerializationError;
+
+    fn try_from(val: UniversalParamsWrapper) -> Result<Self, Self::Error> {
+        checked(&val.data)
     }
 }
 
 impl UniversalParamsWrapper {
-    pub fn data(&self) -> Box<[u8]> {
-        self.data.clone().into_boxed_slice()
+    /// parses and checks params in the canonical encoding, see UniversalParams::check
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SerializationError> {
+        checked::<UniversalParams<E>>(bytes)?;
+        Ok(Self { data: bytes.to_vec() })
     }
 
-    pub fn set_data(&mut self, data: Box<[u8]>) {
-        self.data = data.into_vec();
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.data.clone()
+    }
+
+    pub fn data(&self) -> Box<[u8]> {
+        self.data.clone().into_boxed_slice()
     }
 }
 
@@ -143,28 +176,36 @@ impl From<Ciphertext<E>> for CiphertextWrapper {
 }
 
 impl CiphertextWrapper {
-    pub fn to_ciphertext(&self) -> Ciphertext<E> {
-        Ciphertext::deserialize_uncompressed(&self.data[..]).unwrap()
+    /// the wrapper may come from serde, so its bytes are checked again here
+    pub fn to_ciphertext(&self) -> Result<Ciphertext<E>, SerializationError> {
+        checked(&self.data)
     }
 }
 
-impl From<CiphertextWrapper> for Ciphertext<E> {
-    fn from(wrapper: CiphertextWrapper) -> Self {
+impl TryFrom<CiphertextWrapper> for Ciphertext<E> {
+    type Error = SerializationError;
+
+    fn try_from(wrapper: CiphertextWrapper) -> Result<Self, Self::Error> {
         wrapper.to_ciphertext()
     }
 }
 
 #[wasm_bindgen]
 impl CiphertextWrapper {
+    pub fn from_bytes(bytes: &[u8]) -> Result<CiphertextWrapper, JsError> {
+        checked::<Ciphertext<E>>(bytes)?;
+        Ok(Self { data: bytes.to_vec() })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.data.clone()
+    }
+
+    // read only, as for PublicKeyWrapper
     #[wasm_bindgen(getter)]
     pub fn data(&self) -> Box<[u8]> {
         self.data.clone().into_boxed_slice()
     }
-
-    #[wasm_bindgen(setter)]
-    pub fn set_data(&mut self, data: Box<[u8]>) {
-        self.data = data.into_vec();
-    }
 }
 
 // Wrapper for PairingOutput
@@ -174,6 +215,16 @@ pub struct PairingOutputWrapper<PE: Pairing> {
     pub inner: PairingOutput<PE>,
 }
 
+impl<PE: Pairing> PairingOutputWrapper<PE> {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SerializationError> {
+        Ok(Self { inner: checked(bytes)? })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        to_bytes(&self.inner)
+    }
+}
+
 // Custom serialization for PairingOutput
 fn serialize_pairing_output<S, PE>(value: &PairingOutput<PE>, serializer: S) -> Result<S::Ok, S::Error>
 where
@@ -214,6 +265,7 @@ where
 #[derive(Serialize, Deserialize, Clone)]
 pub struct SerializableAggregateKey {
     pub pk: Vec<PublicKeyWrapper>,
+    pub degree: usize,
     pub agg_sk_li_by_z: Vec<Vec<u8>>, // Serialize G1Projective manually
     pub ask: Vec<u8>, // Serialize G1Projective manually
     pub z_g2: Vec<u8>, // Serialize G2Projective manually
@@ -226,6 +278,7 @@ impl From<AggregateKey<E>> for SerializableAggregateKey {
     fn from(key: AggregateKey<E>) -> Self {
         Self {
             pk: key.pk.into_iter().map(PublicKeyWrapper::from).collect(),
+            degree: key.degree,
             agg_sk_li_by_z: key.agg_sk_li_by_z.into_iter().map(|g| {
                 let mut data = Vec::new();
                 g.serialize_uncompressed(&mut data).unwrap();
@@ -251,18 +304,30 @@ impl From<AggregateKey<E>> for SerializableAggregateKey {
     }
 }
 
-impl From<SerializableAggregateKey> for AggregateKey<E> {
-    fn from(val: SerializableAggregateKey) -> Self {
-        AggregateKey {
-            pk: val.pk.into_iter().map(|p| p.into()).collect(),
-            agg_sk_li_by_z: val.agg_sk_li_by_z.into_iter().map(|g| {
-                <<ark_ec::bls12::Bls12<ark_bls12_381::Config> as ark_ec::pairing::Pairing>::G1 as CanonicalDeserialize>::deserialize_uncompressed(&g[..]).unwrap()
-            }).collect(),
-            ask: <<ark_ec::bls12::Bls12<ark_bls12_381::Config> as ark_ec::pairing::Pairing>::G1 as CanonicalDeserialize>::deserialize_uncompressed(&val.ask[..]).unwrap(),
-            z_g2: <<ark_ec::bls12::Bls12<ark_bls12_381::Config> as ark_ec::pairing::Pairing>::G2 as CanonicalDeserialize>::deserialize_uncompressed(&val.z_g2[..]).unwrap(),
-            h_minus1: <<ark_ec::bls12::Bls12<ark_bls12_381::Config> as ark_ec::pairing::Pairing>::G2 as CanonicalDeserialize>::deserialize_uncompressed(&val.h_minus1[..]).unwrap(),
-            e_gh: val.e_gh,
+impl SerializableAggregateKey {
+    /// parses an aggregate key in the canonical encoding of AggregateKey, checked against params
+    pub fn from_bytes(bytes: &[u8], params: &UniversalParams<E>) -> Result<Self, JsError> {
+        Ok(parse_aggregate_key(bytes, params)?.into())
+    }
+
+    /// the encoding of the public key vector, the wrappers already hold each key's encoding
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = to_bytes(&self.pk.len());
+        for pk in &self.pk {
+            bytes.extend_from_slice(&pk.data);
         }
+        bytes
+    }
+
+    /// aggregates the public keys again over params; the derived fields are not trusted
+    pub fn to_aggregate_key(&self, params: &UniversalParams<E>) -> Result<AggregateKey<E>, JsError> {
+        let pk = self
+            .pk
+            .iter()
+            .cloned()
+            .map(PublicKey::try_from)
+            .collect::<Result<Vec<PublicKey<E>>, _>>()?;
+        AggregateKey::new(pk, params).map_err(|e| JsError::new(&format!("{:?}", e)))
     }
 }
 
@@ -279,6 +344,27 @@ impl ProjectiveG2Wrapper {
         point.serialize_uncompressed(&mut data).unwrap();
         Self { data }
     }
+
+    pub fn to_g2<E: Pairing>(&self) -> Result<E::G2, SerializationError> {
+        E::G2::deserialize_uncompressed(&self.data[..])
+    }
+
+    pub fn from_bytes<E: Pairing>(bytes: &[u8]) -> Result<Self, SerializationError> {
+        checked::<E::G2>(bytes)?;
+        Ok(Self { data: bytes.to_vec() })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.data.clone()
+    }
+
+    /// reads a partial decryption passed from JS as a Uint8Array
+    pub fn from_js_bytes<E: Pairing>(value: &JsValue) -> Result<Self, JsError> {
+        let bytes = value
+            .dyn_ref::<Uint8Array>()
+            .ok_or_else(|| JsError::new("partial decryption must be a Uint8Array"))?;
+        Ok(Self::from_bytes::<E>(&bytes.to_vec())?)
+    }
 }
 
 impl ProjectiveG2Wrapper {
@@ -298,7 +384,7 @@ impl ProjectiveG2Wrapper {
         to_value(&self).unwrap()
     }
 
-    pub fn from_g2_js(js_value: JsValue) -> ProjectiveG2Wrapper {
-        from_value(js_value).unwrap()
+    pub fn from_g2_js(js_value: JsValue) -> Result<ProjectiveG2Wrapper, JsError> {
+        Ok(from_value(js_value)?)
     }
 }
@@ -0,0 +1,200 @@
+// runs the protocol through the C ABI, see tests/ffi.rs
+#include <stdio.h>
+#include <string.h>
+
+#include "silent_threshold.h"
+
+#define N 4
+#define T 2
+
+#define CHECK(expr, expected)                                                     \
+    do {                                                                          \
+        StStatus status_ = (expr);                                                \
+        if (status_ != (expected)) {                                              \
+            fprintf(stderr, "%s:%d: %s returned %d\n", __FILE__, __LINE__, #expr, \
+                    (int)status_);                                                \
+            exit(1);                                                              \
+        }                                                                         \
+    } while (0)
+
+#define OK(expr) CHECK(expr, ST_STATUS_OK)
+
+static uint8_t *read_file(const char *path, size_t *len) {
+    FILE *f = fopen(path, "rb");
+    if (!f) {
+        perror(path);
+        exit(1);
+    }
+    fseek(f, 0, SEEK_END);
+    *len = (size_t)ftell(f);
+    fseek(f, 0, SEEK_SET);
+    uint8_t *bytes = malloc(*len);
+    if (fread(bytes, 1, *len, f) != *len) {
+        perror(path);
+        exit(1);
+    }
+    fclose(f);
+    return bytes;
+}
+
+int main(int argc, char **argv) {
+    if (argc != 3) {
+        fprintf(stderr, "usage: %s params.bin other_params.bin\n", argv[0]);
+        return 2;
+    }
+
+    size_t params_len;
+    uint8_t *params_bytes = read_file(argv[1], &params_len);
+    StParams *params = NULL;
+    OK(st_params_from_bytes(params_bytes, params_len, &params));
+    CHECK(st_params_from_bytes(params_bytes, params_len - 1, &params), ST_STATUS_INVALID_ENCODING);
+    size_t degree;
+    OK(st_params_degree(params, &degree));
+    if (degree != N) {
+        fprintf(stderr, "degree %zu\n", degree);
+        return 1;
+    }
+
+    // keys for slots 1..N-1, round tripping the public keys through bytes
+    StSecretKey *sk[N] = {NULL};
+    const StPublicKey *pk[N - 1];
+    for (size_t id = 1; id < N; id++) {
+        StSecretKey *generated = NULL;
+        OK(st_secret_key_generate(&generated));
+        uint8_t secret[32];
+        size_t secret_len = sizeof secret;
+        OK(st_secret_key_export_secret_bytes(generated, secret, &secret_len));
+        OK(st_secret_key_from_bytes(secret, secret_len, &sk[id]));
+        st_secret_key_free(generated);
+
+        StPublicKey *fresh = NULL;
+        OK(st_secret_key_public_key(sk[id], params, id, N, &fresh));
+
+        size_t len = 0;
+        CHECK(st_public_key_to_bytes(fresh, NULL, &len), ST_STATUS_BUFFER_TOO_SMALL);
+        uint8_t *bytes = malloc(len);
+        OK(st_public_key_to_bytes(fresh, bytes, &len));
+        StPublicKey *parsed = NULL;
+        OK(st_public_key_from_bytes(bytes, len, &parsed));
+        size_t parsed_id;
+        OK(st_public_key_id(parsed, &parsed_id));
+        if (parsed_id != id) {
+            return 1;
+        }
+        pk[N - 1 - id] = parsed; // in reverse order
+        st_public_key_free(fresh);
+        free(bytes);
+    }
+    StPublicKey *stray = NULL;
+    CHECK(st_secret_key_public_key(sk[1], params, 0, N, &stray), ST_STATUS_INVALID_ARGUMENT);
+    CHECK(st_secret_key_public_key(sk[1], params, 1, 3, &stray), ST_STATUS_INVALID_ARGUMENT);
+
+    StAggregateKey *agg_key = NULL;
+    OK(st_aggregate_key_new(pk, N - 1, params, &agg_key));
+    CHECK(st_aggregate_key_new(pk, N - 2, params, &agg_key), ST_STATUS_INVALID_ARGUMENT);
+    size_t size;
+    OK(st_aggregate_key_size(agg_key, &size));
+    if (size != N) {
+        return 1;
+    }
+
+    // the aggregate key travels as its public keys and is aggregated again on arrival
+    size_t agg_len = 0;
+    CHECK(st_aggregate_key_to_bytes(agg_key, NULL, &agg_len), ST_STATUS_BUFFER_TOO_SMALL);
+    uint8_t *agg_bytes = malloc(agg_len);
+    OK(st_aggregate_key_to_bytes(agg_key, agg_bytes, &agg_len));
+    StAggregateKey *received = NULL;
+    OK(st_aggregate_key_from_bytes(agg_bytes, agg_len, params, &received));
+    CHECK(st_aggregate_key_from_bytes(agg_bytes, agg_len - 1, params, &received), ST_STATUS_INVALID_ENCODING);
+    st_aggregate_key_free(received);
+    free(agg_bytes);
+
+    // encrypt, and move the ciphertext through bytes as a service would
+    const uint8_t label[] = "ops";
+    uint8_t key[1024];
+    size_t key_len = 8;
+    StCiphertext *sent = NULL;
+    CHECK(st_encrypt(agg_key, params, T, label, 3, &sent, key, &key_len), ST_STATUS_BUFFER_TOO_SMALL);
+    OK(st_encrypt(agg_key, params, T, label, 3, &sent, key, &key_len));
+    CHECK(st_encrypt(agg_key, params, N, label, 3, &sent, key, &key_len), ST_STATUS_INVALID_ARGUMENT);
+    // params of the same degree from another setup
+    size_t other_len;
+    uint8_t *other_bytes = read_file(argv[2], &other_len);
+    StParams *other = NULL;
+    OK(st_params_from_bytes(other_bytes, other_len, &other));
+    StCiphertext *stray_ct = NULL;
+    CHECK(st_encrypt(agg_key, other, T, label, 3, &stray_ct, key, &key_len), ST_STATUS_MISMATCH);
+    st_params_free(other);
+    free(other_bytes);
+
+    size_t ct_len = 0;
+    CHECK(st_ciphertext_to_bytes(sent, NULL, &ct_len), ST_STATUS_BUFFER_TOO_SMALL);
+    uint8_t *ct_bytes = malloc(ct_len);
+    OK(st_ciphertext_to_bytes(sent, ct_bytes, &ct_len));
+    StCiphertext *ct = NULL;
+    OK(st_ciphertext_from_bytes(ct_bytes, ct_len, &ct));
+    size_t t;
+    OK(st_ciphertext_threshold(ct, &t));
+    if (t != T) {
+        return 1;
+    }
+    OK(st_ciphertext_verify(ct, label, 3, agg_key, params));
+    CHECK(st_ciphertext_verify(ct, label, 2, agg_key, params), ST_STATUS_VERIFICATION_FAILED);
+
+    // partial decryptions from slots 1 and 3
+    uint8_t partial[N][256];
+    size_t partial_len[N] = {0};
+    for (size_t id = 1; id < N; id++) {
+        partial_len[id] = sizeof partial[id];
+        OK(st_partial_decryption(sk[id], ct, label, 3, agg_key, params, partial[id], &partial_len[id]));
+    }
+    // a ciphertext that does not verify gets no answer, so the key cannot be used to sign
+    uint8_t refused[256];
+    size_t refused_len = sizeof refused;
+    CHECK(st_partial_decryption(sk[1], ct, label, 2, agg_key, params, refused, &refused_len),
+          ST_STATUS_VERIFICATION_FAILED);
+    // pk is in reverse order, pk[0] is slot 3
+    OK(st_verify_partial_decryption(pk[0], ct, partial[3], partial_len[3]));
+    CHECK(st_verify_partial_decryption(pk[0], ct, partial[1], partial_len[1]), ST_STATUS_VERIFICATION_FAILED);
+
+    const uint8_t *answers[N] = {NULL, partial[1], NULL, partial[3]};
+    uint8_t combined[1024];
+    size_t combined_len = sizeof combined;
+    OK(st_combine(agg_key, params, ct, answers, partial_len, N, combined, &combined_len));
+    if (combined_len != key_len || memcmp(combined, key, key_len) != 0) {
+        fprintf(stderr, "combined key differs from the encapsulated key\n");
+        return 1;
+    }
+
+    const uint8_t *too_few[N] = {NULL, partial[1], NULL, NULL};
+    CHECK(st_combine(agg_key, params, ct, too_few, partial_len, N, combined, &combined_len),
+          ST_STATUS_THRESHOLD_NOT_MET);
+    const uint8_t *swapped[N] = {NULL, partial[3], NULL, partial[1]};
+    CHECK(st_combine(agg_key, params, ct, swapped, partial_len, N, combined, &combined_len),
+          ST_STATUS_VERIFICATION_FAILED);
+    // a partial past the first t is still checked
+    const uint8_t *extra[N] = {NULL, partial[1], partial[2], partial[1]};
+    CHECK(st_combine(agg_key, params, ct, extra, partial_len, N, combined, &combined_len),
+          ST_STATUS_VERIFICATION_FAILED);
+    size_t truncated[N] = {0, partial_len[1], partial_len[2], partial_len[3] - 1};
+    const uint8_t *malformed[N] = {NULL, partial[1], partial[2], partial[3]};
+    CHECK(st_combine(agg_key, params, ct, malformed, truncated, N, combined, &combined_len),
+          ST_STATUS_INVALID_ENCODING);
+    CHECK(st_combine(agg_key, NULL, ct, answers, partial_len, N, combined, &combined_len),
+          ST_STATUS_NULL_POINTER);
+
+    st_ciphertext_free(ct);
+    st_ciphertext_free(sent);
+    st_aggregate_key_free(agg_key);
+    for (size_t i = 0; i < N - 1; i++) {
+        st_public_key_free((StPublicKey *)pk[i]);
+    }
+    for (size_t id = 1; id < N; id++) {
+        st_secret_key_free(sk[id]);
+    }
+    st_params_free(params);
+    free(ct_bytes);
+    free(params_bytes);
+    printf("ok\n");
+    return 0;
+}
@@ -0,0 +1,119 @@
+//! checks the committed C header against src/ffi.rs, and builds and runs tests/ffi.c
+//! against the cdylib
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use ark_poly::univariate::DensePolynomial;
+use ark_serialize::CanonicalSerialize;
+use silent_threshold::kzg::KZG10;
+
+type E = ark_bls12_381::Bls12_381;
+type UniPoly381 = DensePolynomial<<E as ark_ec::pairing::Pairing>::ScalarField>;
+
+const HEADER: &str = "include/silent_threshold.h";
+
+fn root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn header() -> String {
+    let config = cbindgen::Config::from_file(root().join("cbindgen.toml")).unwrap();
+    let mut bytes = Vec::new();
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(root().join("src/ffi.rs"))
+        .generate()
+        .unwrap()
+        .write(&mut bytes);
+    String::from_utf8(bytes).unwrap()
+}
+
+#[test]
+fn test_header_is_current() {
+    let generated = header();
+    if std::env::var_os("SILENT_THRESHOLD_BLESS").is_some() {
+        fs::write(root().join(HEADER), &generated).unwrap();
+    }
+    let committed = fs::read_to_string(root().join(HEADER)).unwrap_or_default();
+    assert!(
+        committed == generated,
+        "{} is out of date, run SILENT_THRESHOLD_BLESS=1 cargo test --test ffi",
+        HEADER
+    );
+}
+
+#[test]
+fn test_c_client() {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("ffi");
+    fs::create_dir_all(&dir).unwrap();
+
+    // cargo test only builds the rlib. the cdylib gets its own target directory, so this
+    // build neither waits on nor races the cargo running the test, and always uses the dev
+    // profile with the features this test was built with
+    let target_dir = dir.join("target");
+    let mut build = Command::new(env!("CARGO"));
+    build
+        .current_dir(root())
+        .args(["build", "--lib", "--package", "silent-threshold", "--target-dir"])
+        .arg(&target_dir);
+    let features = [
+        ("asm", cfg!(feature = "asm")),
+        ("no-logging", cfg!(feature = "no-logging")),
+        ("parallel", cfg!(feature = "parallel")),
+        ("wasm", cfg!(feature = "wasm")),
+    ];
+    let features: Vec<&str> = features.iter().filter(|(_, on)| *on).map(|(name, _)| *name).collect();
+    if !features.is_empty() {
+        build.arg("--features").arg(features.join(","));
+    }
+    assert!(build.status().unwrap().success(), "building the cdylib failed");
+    let lib_dir = target_dir.join("debug");
+
+    // the C side generates everything but the params, and a second set of params to mix up
+    let mut rng = ark_std::test_rng();
+    let params = KZG10::<E, UniPoly381>::setup(4, &mut rng).unwrap();
+    let params_path = dir.join("params.bin");
+    params.serialize_uncompressed(File::create(&params_path).unwrap()).unwrap();
+    let other = KZG10::<E, UniPoly381>::setup(4, &mut rng).unwrap();
+    let other_path = dir.join("other_params.bin");
+    other.serialize_uncompressed(File::create(&other_path).unwrap()).unwrap();
+
+    fs::write(dir.join("silent_threshold.h"), header()).unwrap();
+    let client = dir.join("ffi_client");
+    let cc = std::env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    let status = Command::new(cc)
+        .arg(root().join("tests/ffi.c"))
+        .arg("-std=c99")
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg("-I")
+        .arg(&dir)
+        .arg("-L")
+        .arg(&lib_dir)
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .arg("-lsilent_threshold")
+        .arg("-o")
+        .arg(&client)
+        .status()
+        .expect("a C compiler, set CC to pick one");
+    assert!(status.success(), "compiling tests/ffi.c failed");
+
+    // cargo puts its own target directory on the library path, which the loader searches
+    // before the rpath, and the cdylib there may be stale
+    let output = Command::new(&client)
+        .arg(&params_path)
+        .arg(&other_path)
+        .env("LD_LIBRARY_PATH", &lib_dir)
+        .env("DYLD_LIBRARY_PATH", &lib_dir)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}